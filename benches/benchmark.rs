@@ -14,30 +14,213 @@ fn roughly_cut(str: &str, size: usize) -> &str {
     &str[..end]
 }
 
+/// Percentages of ASCII, CJK and emoji characters in [`gen_mixed_string`].
+const MIX: [u32; 3] = [70, 20, 10];
+
+/// Synthesizes a string of `len` characters, mixing ASCII, CJK and emoji characters in the ratio
+/// of [`MIX`]. A fixed seed makes the result the same on every run.
+fn gen_mixed_string(len: usize) -> String {
+    const CJK: &[char] = &['你', '好', '吗', '世', '界', '中', '文', '字'];
+    const EMOJI: &[char] = &['👋', '😀', '🎉', '🚀', '🌍'];
+
+    // a linear congruential generator is good enough to spread the characters
+    let mut state: u32 = 0x2545_f491;
+    let mut next = move || {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        state >> 8
+    };
+    (0..len)
+        .map(|_| {
+            let pick = next();
+            let roll = pick % 100;
+            let index = (pick / 100) as usize;
+            if roll < MIX[0] {
+                // printable ASCII, with a space now and then to look like words
+                if index % 6 == 0 {
+                    ' '
+                } else {
+                    char::from(b'a' + (index % 26) as u8)
+                }
+            } else if roll < MIX[0] + MIX[1] {
+                CJK[index % CJK.len()]
+            } else {
+                EMOJI[index % EMOJI.len()]
+            }
+        })
+        .collect()
+}
+
 fn criterion_benchmark(criterion: &mut Criterion) {
     const KB: usize = 1024;
-    const TEXT: &str = include_str!("data/zhufu.txt");
+    const DATASETS: [(&str, &str); 3] = [
+        ("zhu fu", include_str!("data/zhufu.txt")),
+        // emoji ZWJ and modifier sequences, flags and presentation selectors
+        ("emoji", include_str!("data/emoji.txt")),
+        // decomposed diacritics and stacked combining marks
+        ("combining", include_str!("data/combining.txt")),
+    ];
+
+    for (name, text) in DATASETS {
+        for &size in &[KB, 2 * KB, 4 * KB, 8 * KB, 16 * KB, 28 * KB] {
+            let mut group = criterion.benchmark_group(format!("{name}/{size}"));
+            group
+                .sample_size(1000)
+                .measurement_time(Duration::from_secs(20))
+                .throughput(Throughput::Bytes(size as u64));
+            let input = roughly_cut(text, size);
+            let max_width = input.len() / 2;
+            group.bench_function("end", |bench| {
+                bench.iter(|| black_box(input).unicode_truncate(black_box(max_width)));
+            });
+            group.bench_function("start", |bench| {
+                bench.iter(|| black_box(input).unicode_truncate_start(black_box(max_width)));
+            });
+            group.bench_function("centered", |bench| {
+                bench.iter(|| black_box(input).unicode_truncate_centered(black_box(max_width)));
+            });
+            #[cfg(feature = "std")]
+            {
+                use unicode_truncate::Alignment;
 
-    for &size in &[KB, 2 * KB, 4 * KB, 8 * KB, 16 * KB, 28 * KB] {
-        let mut group = criterion.benchmark_group(format!("zhu fu/{size}"));
-        group
-            .sample_size(1000)
-            .measurement_time(Duration::from_secs(20))
-            .throughput(Throughput::Bytes(size as u64));
-        let input = roughly_cut(TEXT, size);
-        let max_width = input.len() / 2;
+                group.bench_function("pad narrow", |bench| {
+                    bench.iter(|| {
+                        black_box(input).unicode_pad(black_box(10), Alignment::Left, false)
+                    });
+                });
+                group.bench_function("pad below", |bench| {
+                    bench.iter(|| {
+                        black_box(input).unicode_pad(black_box(max_width), Alignment::Left, true)
+                    });
+                });
+                // the byte length is more than the display width of the text
+                let above_width = input.len();
+                group.bench_function("pad above", |bench| {
+                    bench.iter(|| {
+                        black_box(input).unicode_pad(black_box(above_width), Alignment::Left, true)
+                    });
+                });
+            }
+            group.finish();
+        }
+    }
+}
+
+/// Measures scaling with the input size on synthetic text, independent of the graphemes of a
+/// particular text.
+fn synthetic_benchmark(criterion: &mut Criterion) {
+    for len in [256, 1024, 4096, 16384] {
+        let input = gen_mixed_string(len);
+        let max_width = unicode_width::UnicodeWidthStr::width(input.as_str()) / 2;
+        let mut group = criterion.benchmark_group(format!("synthetic/{len}"));
+        group.throughput(Throughput::Elements(len as u64));
         group.bench_function("end", |bench| {
-            bench.iter(|| black_box(input).unicode_truncate(black_box(max_width)));
+            bench.iter(|| black_box(input.as_str()).unicode_truncate(black_box(max_width)));
         });
         group.bench_function("start", |bench| {
-            bench.iter(|| black_box(input).unicode_truncate_start(black_box(max_width)));
+            bench.iter(|| black_box(input.as_str()).unicode_truncate_start(black_box(max_width)));
         });
         group.bench_function("centered", |bench| {
-            bench.iter(|| black_box(input).unicode_truncate_centered(black_box(max_width)));
+            bench
+                .iter(|| black_box(input.as_str()).unicode_truncate_centered(black_box(max_width)));
         });
         group.finish();
     }
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn huge_benchmark(criterion: &mut Criterion) {
+    const TEXT: &str = include_str!("data/zhufu.txt");
+
+    // about 1 MB, truncated by a few columns so that only the width pass scales with the input
+    let input = TEXT.repeat(40);
+    let width = unicode_width::UnicodeWidthStr::width(input.as_str());
+    let mut group = criterion.benchmark_group("huge");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    for removal in [4, 100] {
+        group.bench_function(format!("centered remove {removal}"), |bench| {
+            bench.iter(|| {
+                black_box(input.as_str()).unicode_truncate_centered(black_box(width - removal))
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Compares with the naive way of truncating, which counts characters instead of columns.
+fn naive_benchmark(criterion: &mut Criterion) {
+    const TEXT: &str = include_str!("data/zhufu.txt");
+
+    // every character of CJK text takes two columns, so the naive result is twice as wide
+    let naive: String = "你好吗".chars().take(2).collect();
+    assert_eq!(unicode_width::UnicodeWidthStr::width(naive.as_str()), 4);
+    assert_eq!("你好吗".unicode_truncate(2), ("你", 2));
+
+    let input = roughly_cut(TEXT, 1024);
+    let max_width = 80;
+    let mut group = criterion.benchmark_group("naive");
+    group.bench_function("chars take", |bench| {
+        bench.iter(|| {
+            black_box(input)
+                .chars()
+                .take(black_box(max_width))
+                .collect::<String>()
+        });
+    });
+    group.bench_function("unicode_truncate", |bench| {
+        bench.iter(|| black_box(input).unicode_truncate(black_box(max_width)));
+    });
+    group.finish();
+}
+
+#[cfg(feature = "std")]
+fn many_benchmark(criterion: &mut Criterion) {
+    const TEXT: &str = include_str!("data/zhufu.txt");
+
+    // table cells of mixed ASCII and CJK text
+    let cells: Vec<String> = TEXT
+        .lines()
+        .filter(|line| !line.is_empty())
+        .flat_map(|line| {
+            let cjk = roughly_cut(line, 60);
+            [
+                format!("{} {}", cjk.len(), cjk),
+                format!("row {} of the table", line.len()),
+            ]
+        })
+        .collect();
+    let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+    let max_width = 12;
+
+    let mut group = criterion.benchmark_group("many");
+    group.throughput(Throughput::Elements(cells.len() as u64));
+    group.bench_function("loop", |bench| {
+        bench.iter(|| {
+            black_box(&cells)
+                .iter()
+                .map(|cell| cell.unicode_truncate(black_box(max_width)))
+                .collect::<Vec<_>>()
+        });
+    });
+    group.bench_function("truncate_many", |bench| {
+        bench.iter(|| {
+            unicode_truncate::truncate_many(black_box(&cells).iter().copied(), black_box(max_width))
+        });
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function("par_truncate_many", |bench| {
+        bench.iter(|| unicode_truncate::par_truncate_many(black_box(&cells), black_box(max_width)));
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "std"))]
+fn many_benchmark(_criterion: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    synthetic_benchmark,
+    huge_benchmark,
+    naive_benchmark,
+    many_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,153 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Padding into fixed-capacity strings without allocation.
+
+use core::fmt;
+
+use arrayvec::ArrayString;
+
+use crate::{split_padding, Alignment, UnicodeTruncateStr};
+
+/// The error returned by
+/// [`unicode_pad_array`](crate::UnicodeTruncateStr::unicode_pad_array) when the padded string does
+/// not fit in the capacity of the array.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct PadArrayError {
+    required: usize,
+    capacity: usize,
+}
+
+impl PadArrayError {
+    /// Returns the length in bytes the padded string would need.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Returns the capacity in bytes of the array.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for PadArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "padded string needs {} bytes but the capacity is {}",
+            self.required, self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PadArrayError {}
+
+pub(crate) fn pad_array<const N: usize>(
+    s: &str,
+    target_width: usize,
+    align: Alignment,
+    truncate: bool,
+) -> Result<ArrayString<N>, PadArrayError> {
+    let (truncated, columns) = s.unicode_truncate(target_width);
+    let (content, left_pad, right_pad) = if !truncate && truncated.len() < s.len() {
+        (s, 0, 0)
+    } else {
+        let (left_pad, right_pad) = split_padding(target_width.saturating_sub(columns), align);
+        (truncated, left_pad, right_pad)
+    };
+
+    let required = content
+        .len()
+        .saturating_add(left_pad)
+        .saturating_add(right_pad);
+    if required > N {
+        return Err(PadArrayError {
+            required,
+            capacity: N,
+        });
+    }
+
+    let mut result = ArrayString::new();
+    // unwraps are safe as the capacity was checked above
+    for _ in 0..left_pad {
+        result.try_push(' ').unwrap();
+    }
+    result.try_push_str(content).unwrap();
+    for _ in 0..right_pad {
+        result.try_push(' ').unwrap();
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded() {
+        assert_eq!(
+            pad_array::<8>("你", 4, Alignment::Left, true)
+                .unwrap()
+                .as_str(),
+            "你  "
+        );
+        assert_eq!(
+            pad_array::<8>("你", 4, Alignment::Center, true)
+                .unwrap()
+                .as_str(),
+            " 你 "
+        );
+        assert_eq!(
+            pad_array::<8>("你", 4, Alignment::Right, true)
+                .unwrap()
+                .as_str(),
+            "  你"
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(
+            pad_array::<8>("你好吗", 3, Alignment::Left, true)
+                .unwrap()
+                .as_str(),
+            "你 "
+        );
+        assert_eq!(
+            pad_array::<16>("你好吗", 3, Alignment::Left, false)
+                .unwrap()
+                .as_str(),
+            "你好吗"
+        );
+    }
+
+    #[test]
+    fn exact_capacity() {
+        assert_eq!(
+            pad_array::<5>("你", 4, Alignment::Left, true)
+                .unwrap()
+                .as_str(),
+            "你  "
+        );
+    }
+
+    #[test]
+    fn capacity_exceeded() {
+        let err = pad_array::<4>("你", 4, Alignment::Left, true).unwrap_err();
+        assert_eq!((err.required(), err.capacity()), (5, 4));
+        assert_eq!(
+            pad_array::<8>("你好吗", 3, Alignment::Left, false),
+            Err(PadArrayError {
+                required: 9,
+                capacity: 8
+            })
+        );
+    }
+}
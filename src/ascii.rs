@@ -0,0 +1,84 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Display width of ASCII strings in const contexts.
+
+/// Returns the display width of an ASCII string, usable in const contexts.
+///
+/// Every ASCII character is 1 column wide, except that `"\r\n"` is counted as a single column,
+/// the same as [`UnicodeWidthStr::width`](unicode_width::UnicodeWidthStr::width).
+///
+/// # Panics
+/// Panics if `s` contains non-ASCII characters, which is a compile error when evaluated in a const
+/// context.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::const_ascii_width;
+///
+/// const MAX_COL: usize = const_ascii_width("Column Header");
+/// assert_eq!(MAX_COL, 13);
+/// ```
+///
+/// ```compile_fail
+/// use unicode_truncate::const_ascii_width;
+///
+/// const MAX_COL: usize = const_ascii_width("你好");
+/// ```
+pub const fn const_ascii_width(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut width = 0usize;
+    let mut prev = 0u8;
+    let mut i = 0usize;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if !byte.is_ascii() {
+            panic!("const_ascii_width called on a non-ASCII string");
+        }
+        if !(prev == b'\r' && byte == b'\n') {
+            width = width.saturating_add(1);
+        }
+        prev = byte;
+        i = i.saturating_add(1);
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn same_as_width() {
+        for input in [
+            "",
+            "abc",
+            "Column Header",
+            "a\r\nb",
+            "\n\r",
+            "\r\r\n\n",
+            "\0\x1b[m",
+        ] {
+            assert_eq!(const_ascii_width(input), input.width(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn const_context() {
+        const WIDTH: usize = const_ascii_width("id\tname");
+        assert_eq!(WIDTH, 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_ascii() {
+        const_ascii_width("a你");
+    }
+}
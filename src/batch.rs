@@ -0,0 +1,131 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation of many strings to the same width.
+
+use crate::UnicodeTruncateStr;
+
+/// Truncates each string to be at most `max_width` in terms of display width by removing the end
+/// characters. Only available when the `std` feature of this library is activated, and it is
+/// activated by default.
+///
+/// The results are the same as calling
+/// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) on each string, in the same
+/// order. Strings starting with enough plain ASCII are cut without grapheme segmentation, which
+/// makes this faster for tables of mostly ASCII cells.
+///
+/// # Arguments
+/// * `items` - the strings to truncate
+/// * `max_width` - the maximum display width
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::truncate_many;
+///
+/// assert_eq!(
+///     truncate_many(["abcdef", "你好吗", "ab"], 5),
+///     [("abcde", 5), ("你好", 4), ("ab", 2)]
+/// );
+/// ```
+pub fn truncate_many<'a>(
+    items: impl IntoIterator<Item = &'a str>,
+    max_width: usize,
+) -> Vec<(&'a str, usize)> {
+    items
+        .into_iter()
+        .map(|s| truncate_one(s, max_width))
+        .collect()
+}
+
+/// Truncates each string like [`truncate_many`], in parallel using [`rayon`]. Only available when
+/// the `rayon` feature of this library is activated.
+///
+/// The results are in the same order as `items`.
+///
+/// # Arguments
+/// * `items` - the strings to truncate
+/// * `max_width` - the maximum display width
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::par_truncate_many;
+///
+/// assert_eq!(
+///     par_truncate_many(&["abcdef", "你好吗", "ab"], 5),
+///     [("abcde", 5), ("你好", 4), ("ab", 2)]
+/// );
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_truncate_many<'a>(items: &[&'a str], max_width: usize) -> Vec<(&'a str, usize)> {
+    use rayon::prelude::*;
+
+    items
+        .par_iter()
+        .map(|s| truncate_one(s, max_width))
+        .collect()
+}
+
+#[inline]
+fn truncate_one(s: &str, max_width: usize) -> (&str, usize) {
+    truncate_ascii_prefix(s, max_width).unwrap_or_else(|| s.unicode_truncate(max_width))
+}
+
+/// Truncates `s` by slicing if the first `max_width` characters and the one after are plain ASCII.
+///
+/// There is a grapheme boundary between any two ASCII characters except CR LF, and all of them are
+/// 1 column wide except CR LF, which counts as 1 column in total. So excluding CR, the truncated
+/// string is exactly the first `max_width` bytes.
+fn truncate_ascii_prefix(s: &str, max_width: usize) -> Option<(&str, usize)> {
+    let prefix = s.as_bytes().get(..max_width.checked_add(1)?)?;
+    if prefix.iter().all(|&b| b.is_ascii() && b != b'\r') {
+        // unwrap is safe as the prefix is ASCII
+        Some((s.get(..max_width).unwrap(), max_width))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_truncate() {
+        let items = [
+            "",
+            "abc",
+            "abcdefgh",
+            "你好吗你好吗",
+            "ab\r\ncdef",
+            "abc\u{0306}def",
+            "abcd\u{200B}ef",
+            "ab👨‍👩‍👧‍👦cd",
+        ];
+        for max_width in 0..12 {
+            let expected: Vec<_> = items
+                .iter()
+                .map(|s| s.unicode_truncate(max_width))
+                .collect();
+            assert_eq!(truncate_many(items, max_width), expected);
+            #[cfg(feature = "rayon")]
+            assert_eq!(par_truncate_many(&items, max_width), expected);
+        }
+    }
+
+    #[test]
+    fn ascii_prefix() {
+        assert_eq!(truncate_ascii_prefix("abcdef", 3), Some(("abc", 3)));
+        assert_eq!(truncate_ascii_prefix("abcdef", 0), Some(("", 0)));
+        // the next character might combine with the last one
+        assert_eq!(truncate_ascii_prefix("abc\u{0306}", 3), None);
+        assert_eq!(truncate_ascii_prefix("a\r\nb", 2), None);
+        // fits entirely, left to the regular path
+        assert_eq!(truncate_ascii_prefix("abc", 3), None);
+    }
+}
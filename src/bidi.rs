@@ -0,0 +1,127 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Balancing of bidi directional formatting characters after truncation.
+
+use std::borrow::Cow;
+
+/// U+202C POP DIRECTIONAL FORMATTING
+const PDF: char = '\u{202C}';
+/// U+2069 POP DIRECTIONAL ISOLATE
+const PDI: char = '\u{2069}';
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+enum Opener {
+    /// LRE, RLE, LRO or RLO, terminated by PDF
+    Embedding,
+    /// LRI, RLI or FSI, terminated by PDI
+    Isolate,
+}
+
+/// Makes the directional formatting characters in `s` self-contained.
+///
+/// Terminators without a matching opener are dropped, and openers left unterminated get the
+/// necessary PDF/PDI appended. The matching follows UAX #9: a PDI also terminates any embedding
+/// opened after its isolate, a PDF never terminates an isolate, and paragraph separators terminate
+/// everything.
+///
+/// All the characters involved are zero-width, so the display width is unaffected.
+pub(crate) fn balance(s: &str) -> Cow<'_, str> {
+    let mut stack = Vec::new();
+    let mut orphans = Vec::new();
+    for (byte_index, ch) in s.char_indices() {
+        match ch {
+            '\u{202A}' | '\u{202B}' | '\u{202D}' | '\u{202E}' => stack.push(Opener::Embedding),
+            '\u{2066}' | '\u{2067}' | '\u{2068}' => stack.push(Opener::Isolate),
+            PDF => {
+                if stack.last() == Some(&Opener::Embedding) {
+                    stack.pop();
+                } else {
+                    orphans.push(byte_index);
+                }
+            }
+            PDI => {
+                if let Some(isolate) = stack.iter().rposition(|&o| o == Opener::Isolate) {
+                    stack.truncate(isolate);
+                } else {
+                    orphans.push(byte_index);
+                }
+            }
+            // paragraph separators (bidi class B)
+            '\n' | '\r' | '\u{001C}'..='\u{001E}' | '\u{0085}' | '\u{2029}' => stack.clear(),
+            _ => {}
+        }
+    }
+
+    if stack.is_empty() && orphans.is_empty() {
+        return Cow::Borrowed(s);
+    }
+
+    // all terminators are 3 bytes in UTF-8
+    let closers_len = stack.len().saturating_mul(PDI.len_utf8());
+    let mut result = String::with_capacity(s.len().saturating_add(closers_len));
+    let mut last = 0;
+    for orphan in orphans {
+        result.push_str(&s[last..orphan]);
+        // unwrap is safe as both terminators are 3 bytes
+        last = orphan.checked_add(PDI.len_utf8()).unwrap();
+    }
+    result.push_str(&s[last..]);
+    result.extend(stack.iter().rev().map(|opener| match opener {
+        Opener::Embedding => PDF,
+        Opener::Isolate => PDI,
+    }));
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced() {
+        assert!(matches!(balance("abc"), Cow::Borrowed("abc")));
+        assert!(matches!(
+            balance("a\u{2068}b\u{2069}c"),
+            Cow::Borrowed("a\u{2068}b\u{2069}c")
+        ));
+    }
+
+    #[test]
+    fn close_unterminated() {
+        assert_eq!(balance("a\u{2067}b"), "a\u{2067}b\u{2069}");
+        assert_eq!(balance("a\u{202B}b"), "a\u{202B}b\u{202C}");
+        assert_eq!(
+            balance("\u{2067}a\u{202A}b\u{2066}c"),
+            "\u{2067}a\u{202A}b\u{2066}c\u{2069}\u{202C}\u{2069}"
+        );
+    }
+
+    #[test]
+    fn drop_orphans() {
+        assert_eq!(balance("a\u{2069}b"), "ab");
+        assert_eq!(balance("a\u{202C}b\u{2069}"), "ab");
+    }
+
+    #[test]
+    fn isolate_terminates_embeddings() {
+        assert!(matches!(
+            balance("\u{2068}\u{202A}a\u{2069}"),
+            Cow::Borrowed(_)
+        ));
+        // PDF can not terminate an isolate
+        assert_eq!(balance("\u{2068}a\u{202C}"), "\u{2068}a\u{2069}");
+    }
+
+    #[test]
+    fn paragraph_separator_terminates_all() {
+        assert!(matches!(balance("\u{2068}a\nb"), Cow::Borrowed(_)));
+        assert_eq!(balance("\u{2068}a\n\u{2069}b"), "\u{2068}a\nb");
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Composing a line from several pieces within a total width.
+
+use crate::UnicodeTruncateStr;
+
+/// Tracks the columns left in a line composed from several pieces, e.g. the fields of a status
+/// line.
+///
+/// Each piece is truncated like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate)
+/// to the columns that are still free, so the pieces together never exceed the total width. The
+/// budget only does the width accounting and borrows the pieces, so it is available without the
+/// `std` feature.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::WidthBudget;
+///
+/// let mut budget = WidthBudget::new(12);
+/// assert_eq!(budget.push("NORMAL"), ("NORMAL", 6));
+/// assert_eq!(budget.push(" "), (" ", 1));
+/// assert_eq!(budget.push("你好吗.txt"), ("你好", 4));
+/// assert_eq!(budget.remaining(), 1);
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct WidthBudget {
+    remaining: usize,
+}
+
+impl WidthBudget {
+    /// Creates a budget of `total_cols` columns.
+    pub const fn new(total_cols: usize) -> Self {
+        Self {
+            remaining: total_cols,
+        }
+    }
+
+    /// Returns the number of columns left.
+    pub const fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns `true` if no columns are left.
+    pub const fn is_full(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Truncates `s` to the remaining columns, and takes the width of the result from the budget.
+    ///
+    /// A wide character that does not fit in the remaining columns is cut off, so columns may be
+    /// left over even though `s` was truncated. Zero-width strings are returned whole even when the
+    /// budget is full.
+    ///
+    /// # Arguments
+    /// * `s` - the piece to add to the line
+    pub fn push<'a>(&mut self, s: &'a str) -> (&'a str, usize) {
+        let (result, width) = s.unicode_truncate(self.remaining);
+        self.remaining = self.remaining.saturating_sub(width);
+        (result, width)
+    }
+
+    /// Truncates `s` to a field of `cols` columns, and takes the whole field from the budget,
+    /// however wide the result is.
+    ///
+    /// This is for fixed-width fields, where the caller pads the result to `cols` columns, e.g.
+    /// with [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad). A field wider than the
+    /// remaining columns is shrunk to them, so the budget is full afterwards.
+    ///
+    /// # Arguments
+    /// * `s` - the piece to add to the line
+    /// * `cols` - the width of the field
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::WidthBudget;
+    ///
+    /// let mut budget = WidthBudget::new(10);
+    /// assert_eq!(budget.push_padded("42", 4), ("42", 2));
+    /// assert_eq!(budget.remaining(), 6);
+    /// assert_eq!(budget.push_padded("你好吗", 8), ("你好吗", 6));
+    /// assert!(budget.is_full());
+    /// ```
+    pub fn push_padded<'a>(&mut self, s: &'a str, cols: usize) -> (&'a str, usize) {
+        let field = cols.min(self.remaining);
+        // unwrap is safe as field is at most remaining
+        self.remaining = self.remaining.checked_sub(field).unwrap();
+        s.unicode_truncate(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_until_full() {
+        let mut budget = WidthBudget::new(5);
+        assert_eq!(budget.push("abc"), ("abc", 3));
+        assert_eq!(budget.push("你好"), ("你", 2));
+        assert!(budget.is_full());
+        assert_eq!(budget.push("abc"), ("", 0));
+        assert_eq!(budget.push("\u{200B}"), ("\u{200B}", 0));
+        assert_eq!(budget.remaining(), 0);
+    }
+
+    #[test]
+    fn wide_char_leaves_column() {
+        let mut budget = WidthBudget::new(2);
+        assert_eq!(budget.push("a你"), ("a", 1));
+        assert_eq!(budget.remaining(), 1);
+        assert_eq!(budget.push("你"), ("", 0));
+        assert_eq!(budget.push("b"), ("b", 1));
+        assert!(budget.is_full());
+    }
+
+    #[test]
+    fn push_padded_takes_whole_field() {
+        let mut budget = WidthBudget::new(8);
+        assert_eq!(budget.push_padded("a", 3), ("a", 1));
+        assert_eq!(budget.remaining(), 5);
+        assert_eq!(budget.push_padded("你好吗", 3), ("你", 2));
+        assert_eq!(budget.remaining(), 2);
+        assert_eq!(budget.push_padded("abc", 5), ("ab", 2));
+        assert!(budget.is_full());
+        assert_eq!(budget.push_padded("abc", 5), ("", 0));
+    }
+}
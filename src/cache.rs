@@ -0,0 +1,163 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Caching of truncation results for immutable strings.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::UnicodeTruncateStr;
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+struct Key {
+    ptr: usize,
+    len: usize,
+    max_width: usize,
+}
+
+/// A small LRU cache of [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate)
+/// results. Only available when the `cache` feature of this library is activated.
+///
+/// The results are keyed by the address and length of the string together with the requested
+/// width, which makes lookups O(1) in the string length. This is useful for UIs that truncate the
+/// same static text to the same width on every frame.
+///
+/// # Aliasing
+/// Because the content of the string is not part of the key, the caller must make sure that a
+/// given address and length always refer to the same content while the cache is in use. This holds
+/// for `&'static str` and for strings that are neither mutated nor freed during the cache's
+/// lifetime. If a string might be dropped and its memory reused for different content, call
+/// [`clear`](Self::clear) first. Violating this never causes undefined behavior, but stale
+/// results may be returned.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::TruncateCache;
+///
+/// let mut cache = TruncateCache::new(16);
+/// let text = "你好吗";
+/// let (range, width) = cache.get_or_truncate(text, 5);
+/// assert_eq!((&text[range], width), ("你好", 4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TruncateCache {
+    capacity: usize,
+    // most recently used at the front
+    entries: VecDeque<(Key, Range<usize>, usize)>,
+}
+
+impl TruncateCache {
+    /// Creates a cache holding at most `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the cached truncation of `s` to `max_width`, computing and caching it if missing.
+    ///
+    /// The result is the byte range of the truncated string in `s` and its display width, as in
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate).
+    ///
+    /// # Arguments
+    /// * `s` - the string to truncate, see the aliasing requirements on [`TruncateCache`]
+    /// * `max_width` - the maximum display width
+    pub fn get_or_truncate(&mut self, s: &str, max_width: usize) -> (Range<usize>, usize) {
+        let key = Key {
+            ptr: s.as_ptr() as usize,
+            len: s.len(),
+            max_width,
+        };
+
+        if let Some(pos) = self.entries.iter().position(|(k, _, _)| *k == key) {
+            // unwrap is safe as pos comes from the iteration above
+            let entry = self.entries.remove(pos).unwrap();
+            let result = (entry.1.clone(), entry.2);
+            self.entries.push_front(entry);
+            return result;
+        }
+
+        let (truncated, width) = s.unicode_truncate(max_width);
+        let range = 0..truncated.len();
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity {
+                self.entries.pop_back();
+            }
+            self.entries.push_front((key, range.clone(), width));
+        }
+        (range, width)
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there is no cached result.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_truncate() {
+        let mut cache = TruncateCache::new(4);
+        let input = "你好吗";
+        for _ in 0..2 {
+            for max_width in 0..8 {
+                let (range, width) = cache.get_or_truncate(input, max_width);
+                assert_eq!((&input[range], width), input.unicode_truncate(max_width));
+            }
+        }
+    }
+
+    #[test]
+    fn keyed_by_pointer_length_and_width() {
+        let mut cache = TruncateCache::new(4);
+        let input = "abcabc";
+        assert_eq!(cache.get_or_truncate(&input[..3], 2), (0..2, 2));
+        assert_eq!(cache.get_or_truncate(&input[3..], 2), (0..2, 2));
+        assert_eq!(cache.get_or_truncate(&input[..2], 2), (0..2, 2));
+        assert_eq!(cache.get_or_truncate(&input[..3], 1), (0..1, 1));
+        assert_eq!(cache.len(), 4);
+        assert_eq!(cache.get_or_truncate(&input[..3], 2), (0..2, 2));
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = TruncateCache::new(2);
+        let input = "abc";
+        cache.get_or_truncate(input, 1);
+        cache.get_or_truncate(input, 2);
+        // refresh width 1 so that width 2 is evicted next
+        cache.get_or_truncate(input, 1);
+        cache.get_or_truncate(input, 3);
+        assert_eq!(cache.len(), 2);
+        let widths: Vec<_> = cache.entries.iter().map(|(k, _, _)| k.max_width).collect();
+        assert_eq!(widths, [3, 1]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut cache = TruncateCache::new(0);
+        assert_eq!(cache.get_or_truncate("abc", 2), (0..2, 2));
+        assert!(cache.is_empty());
+    }
+}
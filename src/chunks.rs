@@ -0,0 +1,421 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Splitting strings into consecutive chunks of limited display width.
+
+use core::iter::FusedIterator;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::smart::truncate_at_word_boundary;
+use crate::UnicodeTruncateStr;
+
+/// Iterator over consecutive chunks of a string, each at most `max_width` in terms of display
+/// width.
+///
+/// Created by [`unicode_chunks`](crate::UnicodeTruncateStr::unicode_chunks). Chunks are computed
+/// lazily from the remainder of the string and borrow from it, so no allocation is needed.
+///
+/// Zero-width characters are kept with the chunk before them. The iteration stops early at a
+/// grapheme wider than `max_width`, which is left in the [`remainder`](Self::remainder).
+#[derive(Debug, Clone)]
+pub struct UnicodeChunks<'a> {
+    remainder: &'a str,
+    max_width: usize,
+}
+
+impl<'a> UnicodeChunks<'a> {
+    pub(crate) fn new(s: &'a str, max_width: usize) -> Self {
+        Self {
+            remainder: s,
+            max_width,
+        }
+    }
+
+    /// Returns the part of the string not yielded yet.
+    pub fn remainder(&self) -> &'a str {
+        self.remainder
+    }
+}
+
+impl<'a> Iterator for UnicodeChunks<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remainder.is_empty() || self.max_width == 0 {
+            return None;
+        }
+        let (chunk, width) = self.remainder.unicode_truncate(self.max_width);
+        if chunk.is_empty() || (width == 0 && chunk.len() < self.remainder.len()) {
+            // the next grapheme is wider than max_width and can never be yielded
+            return None;
+        }
+        self.remainder = &self.remainder[chunk.len()..];
+        Some((chunk, width))
+    }
+}
+
+impl FusedIterator for UnicodeChunks<'_> {}
+
+/// A chunk of a string yielded by
+/// [`unicode_column_chunks`](crate::UnicodeTruncateStr::unicode_column_chunks).
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Chunk<'a> {
+    /// The text of the chunk, borrowed from the string.
+    pub text: &'a str,
+    /// The display width of the text.
+    pub width: usize,
+    /// The display column the chunk starts at, i.e. the total width of the chunks before it.
+    pub start_col: usize,
+}
+
+/// Iterator over consecutive chunks of a string like [`UnicodeChunks`], which also reports the
+/// column each chunk starts at.
+///
+/// Created by [`unicode_column_chunks`](crate::UnicodeTruncateStr::unicode_column_chunks).
+#[derive(Debug, Clone)]
+pub struct UnicodeColumnChunks<'a> {
+    chunks: UnicodeChunks<'a>,
+    column: usize,
+}
+
+impl<'a> UnicodeColumnChunks<'a> {
+    pub(crate) fn new(s: &'a str, max_width: usize) -> Self {
+        Self {
+            chunks: UnicodeChunks::new(s, max_width),
+            column: 0,
+        }
+    }
+
+    /// Returns the part of the string not yielded yet.
+    pub fn remainder(&self) -> &'a str {
+        self.chunks.remainder()
+    }
+}
+
+impl<'a> Iterator for UnicodeColumnChunks<'a> {
+    type Item = Chunk<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (text, width) = self.chunks.next()?;
+        let start_col = self.column;
+        self.column = self.column.saturating_add(width);
+        Some(Chunk {
+            text,
+            width,
+            start_col,
+        })
+    }
+}
+
+impl FusedIterator for UnicodeColumnChunks<'_> {}
+
+/// Iterator over the lines of a string wrapped at `max_width` in terms of display width, without
+/// regard to words.
+///
+/// Created by [`unicode_wrap_hard`](crate::UnicodeTruncateStr::unicode_wrap_hard). Like
+/// [`UnicodeChunks`], lines borrow from the string and zero-width characters are kept with the line
+/// before them. Unlike it, a grapheme wider than `max_width` is yielded on a line of its own, so
+/// the lines always concatenate to the whole string.
+#[derive(Debug, Clone)]
+pub struct UnicodeWrapHard<'a> {
+    remainder: &'a str,
+    max_width: usize,
+}
+
+impl<'a> UnicodeWrapHard<'a> {
+    pub(crate) fn new(s: &'a str, max_width: usize) -> Self {
+        Self {
+            remainder: s,
+            max_width,
+        }
+    }
+
+    /// Returns the part of the string not yielded yet.
+    pub fn remainder(&self) -> &'a str {
+        self.remainder
+    }
+}
+
+impl<'a> Iterator for UnicodeWrapHard<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remainder.is_empty() {
+            return None;
+        }
+        let (mut line, mut width) = self.remainder.unicode_truncate(self.max_width);
+        if width == 0 && line.len() < self.remainder.len() {
+            // the next grapheme is wider than max_width, so it is put on a line of its own along
+            // with the zero-width characters around it
+            // unwrap is safe as the remainder is not exhausted
+            let wide = self.remainder[line.len()..].graphemes(true).next().unwrap();
+            let (own_line, own_width) = self.remainder.unicode_truncate(wide.width());
+            line = own_line;
+            width = own_width;
+        }
+        self.remainder = &self.remainder[line.len()..];
+        Some((line, width))
+    }
+}
+
+impl FusedIterator for UnicodeWrapHard<'_> {}
+
+/// Iterator over the lines of a string wrapped at `max_width` in terms of display width, breaking
+/// at word boundaries.
+///
+/// Created by [`unicode_wrap_words`](crate::UnicodeTruncateStr::unicode_wrap_words). Lines borrow
+/// from the string and are measured like
+/// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), so they never split a
+/// grapheme and zero-width characters are kept with the line before them.
+#[derive(Debug, Clone)]
+pub struct UnicodeWrapWords<'a> {
+    remainder: &'a str,
+    max_width: usize,
+}
+
+impl<'a> UnicodeWrapWords<'a> {
+    pub(crate) fn new(s: &'a str, max_width: usize) -> Self {
+        Self {
+            remainder: s,
+            max_width,
+        }
+    }
+
+    /// Returns the part of the string not yielded yet.
+    pub fn remainder(&self) -> &'a str {
+        self.remainder
+    }
+
+    /// Returns the next line together with its display width.
+    pub(crate) fn next_line(&mut self) -> Option<(&'a str, usize)> {
+        if self.remainder.is_empty() {
+            return None;
+        }
+        let (line, after) = match self.remainder.find('\n') {
+            // unwrap is safe as the newline is a single byte
+            Some(newline) => (
+                &self.remainder[..newline],
+                &self.remainder[newline.checked_add(1).unwrap()..],
+            ),
+            None => (self.remainder, ""),
+        };
+        let content = line.trim();
+        let (chunk, width) = content.unicode_truncate(self.max_width);
+        if chunk.len() == content.len() {
+            self.remainder = after;
+            return Some((content, width));
+        }
+
+        let (piece, width) = match truncate_at_word_boundary(content, chunk.len()) {
+            // a word wider than the line, or a grapheme wider than the line on its own
+            // unwrap is safe as the content is wider than the line, so it is not empty
+            ("", _) => content.unicode_wrap_hard(self.max_width).next().unwrap(),
+            words => words,
+        };
+        self.remainder = if content[piece.len()..].trim_start().is_empty() {
+            after
+        } else {
+            // the whitespace at the break is trimmed with the next line
+            let indent = line.len().saturating_sub(line.trim_start().len());
+            &self.remainder[indent.saturating_add(piece.len())..]
+        };
+        Some((piece, width))
+    }
+}
+
+impl<'a> Iterator for UnicodeWrapWords<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().map(|(line, _)| line)
+    }
+}
+
+impl FusedIterator for UnicodeWrapWords<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_width_chars_stay_with_preceding_chunk() {
+        let mut chunks = "ab\u{0306}\u{200B}cd".unicode_chunks(2);
+        assert_eq!(chunks.next(), Some(("ab\u{0306}\u{200B}", 2)));
+        assert_eq!(chunks.next(), Some(("cd", 2)));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn only_zero_width() {
+        let mut chunks = "\u{200B}".unicode_chunks(2);
+        assert_eq!(chunks.next(), Some(("\u{200B}", 0)));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn stops_at_grapheme_too_wide() {
+        let mut chunks = "ab你c".unicode_chunks(1);
+        assert_eq!(chunks.next(), Some(("a", 1)));
+        assert_eq!(chunks.next(), Some(("b", 1)));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), "你c");
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn zero_max_width() {
+        assert_eq!("abc".unicode_chunks(0).next(), None);
+        assert_eq!("".unicode_chunks(3).next(), None);
+    }
+
+    #[test]
+    fn wrap_hard_same_as_chunks() {
+        let mut lines = [("", 0); 4];
+        for (slot, line) in lines.iter_mut().zip("你好吗abc".unicode_wrap_hard(3)) {
+            *slot = line;
+        }
+        assert_eq!(lines, [("你", 2), ("好", 2), ("吗a", 3), ("bc", 2)]);
+    }
+
+    #[test]
+    fn wrap_hard_family() {
+        let family = "👨‍👩‍👧‍👦";
+        let mut lines = "👨‍👩‍👧‍👦👨‍👩‍👧‍👦👨‍👩‍👧‍👦".unicode_wrap_hard(4);
+        assert_eq!(
+            lines.next().map(|(line, width)| (line.len(), width)),
+            Some((2 * family.len(), 4))
+        );
+        assert_eq!(lines.next(), Some((family, 2)));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn wrap_hard_too_wide_on_own_line() {
+        let family = "👨‍👩‍👧‍👦";
+        let mut lines = "a👨‍👩‍👧‍👦\u{200B}b".unicode_wrap_hard(1);
+        assert_eq!(lines.next(), Some(("a", 1)));
+        assert_eq!(
+            lines.next().map(|(line, width)| (line.len(), width)),
+            Some((family.len() + 3, 2))
+        );
+        assert_eq!(lines.next(), Some(("b", 1)));
+        assert_eq!(lines.next(), None);
+        assert_eq!(lines.remainder(), "");
+
+        let mut lines = "\u{200B}你".unicode_wrap_hard(1);
+        assert_eq!(lines.next(), Some(("\u{200B}你", 2)));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn wrap_hard_zero_max_width() {
+        let mut lines = "a你\u{200B}".unicode_wrap_hard(0);
+        assert_eq!(lines.next(), Some(("a", 1)));
+        assert_eq!(lines.next(), Some(("你\u{200B}", 2)));
+        assert_eq!(lines.next(), None);
+        assert_eq!(
+            "\u{200B}".unicode_wrap_hard(0).next(),
+            Some(("\u{200B}", 0))
+        );
+        assert_eq!("".unicode_wrap_hard(3).next(), None);
+    }
+
+    #[test]
+    fn wrap_words_greedy() {
+        assert!("The quick brown fox"
+            .unicode_wrap_words(10)
+            .eq(["The quick", "brown fox"]));
+        assert!("ab   cd  ".unicode_wrap_words(3).eq(["ab", "cd"]));
+        assert!("  你好吗 abc"
+            .unicode_wrap_words(4)
+            .eq(["你好", "吗", "abc"]));
+        assert_eq!("".unicode_wrap_words(3).next(), None);
+    }
+
+    #[test]
+    fn wrap_words_newlines() {
+        assert!("one\r\n\ntwo three"
+            .unicode_wrap_words(10)
+            .eq(["one", "", "two three"]));
+        assert!("one\n".unicode_wrap_words(10).eq(["one"]));
+        assert!("one   \ntwo".unicode_wrap_words(3).eq(["one", "two"]));
+    }
+
+    #[test]
+    fn wrap_words_long_words() {
+        assert!("abcdefgh ij"
+            .unicode_wrap_words(3)
+            .eq(["abc", "def", "gh", "ij"]));
+        // a grapheme wider than the line is put on a line by itself
+        assert!("a你b".unicode_wrap_words(1).eq(["a", "你", "b"]));
+        assert!("你".unicode_wrap_words(0).eq(["你"]));
+    }
+
+    #[test]
+    fn wrap_words_measured_like_truncate() {
+        for input in [
+            "y\u{0306}es a\u{200B}b 你好 👨‍👩‍👧‍👦 \u{1F1E6}\u{1F1F8}",
+            "e\u{301}\u{301} \u{200B}\u{200B} x",
+        ] {
+            for max_width in 1..8 {
+                let mut lines = input.unicode_wrap_words(max_width);
+                while let Some((line, width)) = lines.next_line() {
+                    // only a grapheme on a line by itself is wider than max_width
+                    assert_eq!(
+                        line.unicode_truncate(max_width.max(width)),
+                        (line, width),
+                        "{:?}",
+                        line
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn column_chunks_start_col() {
+        let mut chunks = "你好吗abc".unicode_column_chunks(3);
+        let expected = [("你", 2, 0), ("好", 2, 2), ("吗a", 3, 4), ("bc", 2, 7)];
+        for (text, width, start_col) in expected {
+            assert_eq!(
+                chunks.next(),
+                Some(Chunk {
+                    text,
+                    width,
+                    start_col
+                })
+            );
+        }
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn column_chunks_same_as_chunks() {
+        for input in ["", "abc", "你好吗", "ab\u{0306}\u{200B}cd", "a你b"] {
+            for max_width in 0..5 {
+                let column_chunks = input.unicode_column_chunks(max_width);
+                let chunks = input.unicode_chunks(max_width);
+                assert!(column_chunks
+                    .map(|chunk| (chunk.text, chunk.width))
+                    .eq(chunks));
+            }
+        }
+    }
+
+    #[test]
+    fn column_chunks_zero_width() {
+        assert_eq!("abc".unicode_column_chunks(0).next(), None);
+        // a grapheme wider than the chunks stops the iteration
+        let mut chunks = "a你b".unicode_column_chunks(1);
+        assert_eq!(chunks.nth(1), None);
+        assert_eq!(chunks.remainder(), "你b");
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Padding with fill characters chosen at runtime.
+
+use crate::char_width;
+
+/// Decides the characters padding is filled with, for
+/// [`unicode_pad_strategy`](crate::UnicodeTruncateStr::unicode_pad_strategy). Only available when
+/// the `std` feature of this library is activated, and it is activated by default.
+///
+/// The padding is made of characters of [`fill_width`](FillStrategy::fill_width) columns each, and
+/// the columns they can not cover are filled with spaces on the outer side, like for
+/// [`unicode_pad_into`](crate::UnicodeTruncateStr::unicode_pad_into). A width of 0 is treated as 1.
+pub trait FillStrategy {
+    /// Returns the character to put at display column `col` of the padded string, counted from 0.
+    /// It should be [`fill_width`](FillStrategy::fill_width) columns wide.
+    fn fill_char(&self, col: usize) -> char;
+
+    /// Returns the display width of the characters returned by
+    /// [`fill_char`](FillStrategy::fill_char).
+    fn fill_width(&self) -> usize;
+}
+
+/// Fills padding with spaces, like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad).
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct SpaceFill;
+
+impl FillStrategy for SpaceFill {
+    fn fill_char(&self, _col: usize) -> char {
+        ' '
+    }
+
+    fn fill_width(&self) -> usize {
+        1
+    }
+}
+
+/// Fills padding with a single character, which may be wide.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct CharFill(pub char);
+
+impl FillStrategy for CharFill {
+    fn fill_char(&self, _col: usize) -> char {
+        self.0
+    }
+
+    fn fill_width(&self) -> usize {
+        char_width(self.0)
+    }
+}
+
+/// Fills padding by repeating a pattern of characters, one per column.
+///
+/// The pattern is aligned to the columns of the padded string rather than to the start of the
+/// padding, so e.g. a dotted leader lines up across rows of different lengths. Characters of the
+/// pattern not 1 column wide are replaced with spaces, and an empty pattern fills with spaces.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct PatternFill<'a>(pub &'a str);
+
+impl FillStrategy for PatternFill<'_> {
+    fn fill_char(&self, col: usize) -> char {
+        // an empty pattern has no remainder
+        col.checked_rem(self.0.chars().count())
+            .and_then(|index| self.0.chars().nth(index))
+            .filter(|&c| char_width(c) == 1)
+            .unwrap_or(' ')
+    }
+
+    fn fill_width(&self) -> usize {
+        1
+    }
+}
+
+/// Surrounds `content` of `content_width` columns with `left_pad` and `right_pad` columns of
+/// padding from `fill`.
+pub(crate) fn pad_with(
+    content: &str,
+    content_width: usize,
+    left_pad: usize,
+    right_pad: usize,
+    fill: &dyn FillStrategy,
+) -> String {
+    let mut result = String::with_capacity(
+        content
+            .len()
+            .saturating_add(left_pad)
+            .saturating_add(right_pad),
+    );
+    push_fill(&mut result, 0, left_pad, fill, true);
+    result.push_str(content);
+    let right_start = left_pad.saturating_add(content_width);
+    push_fill(&mut result, right_start, right_pad, fill, false);
+    result
+}
+
+/// Appends `cols` columns of padding from `fill` starting at column `start_col`, with the spaces
+/// the fill can not cover on the left if `spaces_first`, or on the right otherwise.
+fn push_fill(
+    buf: &mut String,
+    start_col: usize,
+    cols: usize,
+    fill: &dyn FillStrategy,
+    spaces_first: bool,
+) {
+    let fill_width = fill.fill_width().max(1);
+    // unwrap is safe as fill_width is at least 1
+    let fills = cols.checked_div(fill_width).unwrap();
+    let spaces = cols.checked_rem(fill_width).unwrap();
+
+    let mut col = start_col;
+    if spaces_first {
+        buf.extend(core::iter::repeat(' ').take(spaces));
+        col = col.saturating_add(spaces);
+    }
+    for _ in 0..fills {
+        buf.push(fill.fill_char(col));
+        col = col.saturating_add(fill_width);
+    }
+    if !spaces_first {
+        buf.extend(core::iter::repeat(' ').take(spaces));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_by_column() {
+        let dots = PatternFill("-.");
+        assert_eq!(pad_with("ab", 2, 3, 2, &dots), "-.-ab.-");
+        assert_eq!(pad_with("abc", 3, 2, 2, &dots), "-.abc.-");
+    }
+
+    #[test]
+    fn pattern_wide_or_empty() {
+        assert_eq!(pad_with("", 0, 0, 4, &PatternFill("a你")), "a a ");
+        assert_eq!(pad_with("", 0, 0, 3, &PatternFill("")), "   ");
+    }
+
+    #[test]
+    fn wide_char_spaces_outside() {
+        let fill = CharFill('＊');
+        assert_eq!(pad_with("ab", 2, 3, 3, &fill), " ＊ab＊ ");
+        assert_eq!(pad_with("ab", 2, 1, 0, &fill), " ab");
+    }
+
+    #[test]
+    fn zero_width_takes_a_column() {
+        assert_eq!(
+            pad_with("a", 1, 0, 2, &CharFill('\u{200B}')),
+            "a\u{200B}\u{200B}"
+        );
+    }
+}
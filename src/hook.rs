@@ -0,0 +1,116 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reporting width mismatches to a hook instead of panicking.
+
+use std::sync::RwLock;
+
+/// A truncation result whose reported width differs from its measured width, passed to the hook
+/// registered with [`set_width_mismatch_hook`].
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct WidthMismatch<'a> {
+    result: &'a str,
+    reported_width: usize,
+    actual_width: usize,
+}
+
+impl<'a> WidthMismatch<'a> {
+    /// Returns the truncated string.
+    pub fn result(&self) -> &'a str {
+        self.result
+    }
+
+    /// Returns the width returned along with the truncated string.
+    pub fn reported_width(&self) -> usize {
+        self.reported_width
+    }
+
+    /// Returns the width of the truncated string measured grapheme by grapheme.
+    pub fn actual_width(&self) -> usize {
+        self.actual_width
+    }
+}
+
+static HOOK: RwLock<Option<fn(&WidthMismatch<'_>)>> = RwLock::new(None);
+
+/// Registers `hook` to be called when a truncation result does not have the width returned with
+/// it, or removes the hook with `None`. Only available when the `mismatch-hook` feature of this
+/// library is activated.
+///
+/// Results are only checked in builds with debug assertions. Without a hook, a mismatch panics
+/// like a failed `debug_assert_eq!`. With a hook, it is called instead and the result is returned
+/// as usual, so e.g. a fuzzer can collect mismatches and keep running. The hook is global and
+/// replaces any hook registered before.
+///
+/// # Arguments
+/// * `hook` - the function to call with each mismatch
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::{set_width_mismatch_hook, WidthMismatch};
+///
+/// fn log_mismatch(mismatch: &WidthMismatch<'_>) {
+///     eprintln!(
+///         "{:?} reported as {} columns but is {}",
+///         mismatch.result(),
+///         mismatch.reported_width(),
+///         mismatch.actual_width()
+///     );
+/// }
+///
+/// set_width_mismatch_hook(Some(log_mismatch));
+/// set_width_mismatch_hook(None);
+/// ```
+pub fn set_width_mismatch_hook(hook: Option<fn(&WidthMismatch<'_>)>) {
+    // a poisoned lock still holds a valid function pointer
+    *HOOK.write().unwrap_or_else(|err| err.into_inner()) = hook;
+}
+
+/// Calls the registered hook with the mismatch, and returns whether there was one.
+pub(crate) fn report(result: &str, reported_width: usize, actual_width: usize) -> bool {
+    let hook = *HOOK.read().unwrap_or_else(|err| err.into_inner());
+    match hook {
+        Some(hook) => {
+            hook(&WidthMismatch {
+                result,
+                reported_width,
+                actual_width,
+            });
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static REPORTED: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_mismatch(mismatch: &WidthMismatch<'_>) {
+        assert_eq!(mismatch.result(), "你好");
+        assert_eq!((mismatch.reported_width(), mismatch.actual_width()), (3, 4));
+        REPORTED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn registered_hook_called() {
+        set_width_mismatch_hook(Some(count_mismatch));
+        assert!(report("你好", 3, 4));
+        assert!(crate::report_width_mismatch("你好", 3, 4));
+        assert_eq!(REPORTED.load(Ordering::SeqCst), 2);
+
+        set_width_mismatch_hook(None);
+        assert!(!report("你好", 3, 4));
+        assert_eq!(REPORTED.load(Ordering::SeqCst), 2);
+    }
+}
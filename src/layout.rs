@@ -0,0 +1,243 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fitting rows of cells into a total width.
+
+use core::convert::TryFrom;
+
+use crate::{Alignment, UnicodeTruncateStr};
+
+/// Shrinks column widths proportionally to fit in `total_budget` columns. Only available when the
+/// `std` feature of this library is activated, and it is activated by default.
+///
+/// If the `natural` widths fit in the budget, they are returned unchanged. Otherwise each column
+/// keeps its minimum width, and the rest of the budget is shared in proportion to how much each
+/// column can shrink, i.e. its natural width minus its minimum. So no column gets wider than its
+/// natural width, and a column that is already at its minimum is not shrunk further. Columns left
+/// over by rounding down are handed out one at a time from left to right, so the result is
+/// deterministic and uses the whole budget.
+///
+/// A minimum larger than the natural width is lowered to the natural width, and missing minimums
+/// are 0. If the minimums alone exceed the budget, they are returned as they are, so the result is
+/// wider than `total_budget` and the caller has to decide what to drop.
+///
+/// # Arguments
+/// * `natural` - the widths the columns would like to have
+/// * `minimums` - the widths the columns must not shrink below
+/// * `total_budget` - the total width available
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::distribute_widths;
+///
+/// assert_eq!(distribute_widths(&[10, 20], &[0, 0], 40), [10, 20]);
+/// assert_eq!(distribute_widths(&[10, 20], &[0, 0], 15), [5, 10]);
+/// assert_eq!(distribute_widths(&[10, 20], &[8, 0], 15), [9, 6]);
+/// assert_eq!(distribute_widths(&[10, 20], &[8, 10], 15), [8, 10]);
+/// ```
+pub fn distribute_widths(natural: &[usize], minimums: &[usize], total_budget: usize) -> Vec<usize> {
+    let mins: Vec<usize> = natural
+        .iter()
+        .enumerate()
+        .map(|(i, &width)| minimums.get(i).map_or(0, |&min| min.min(width)))
+        .collect();
+    // sums are taken in u128, so even many columns of usize::MAX do not overflow
+    let sum = |widths: &[usize]| {
+        widths
+            .iter()
+            .fold(0u128, |total, &width| total.saturating_add(width as u128))
+    };
+    let budget = total_budget as u128;
+    if sum(natural) <= budget {
+        return natural.to_vec();
+    }
+    let min_total = sum(&mins);
+    if min_total >= budget {
+        return mins;
+    }
+
+    // unwrap is safe as the minimums fit in the budget
+    let available = budget.checked_sub(min_total).unwrap();
+    // available is less than total_slack, as the natural widths do not fit in the budget
+    let total_slack = sum(natural).saturating_sub(min_total);
+    let mut widths = Vec::with_capacity(natural.len());
+    let mut distributed = 0u128;
+    for (&width, &min) in natural.iter().zip(&mins) {
+        // unwrap is safe as min is at most width
+        let slack = width.checked_sub(min).unwrap() as u128;
+        // unwrap is safe as total_slack is not zero, and the share is at most slack
+        let share = available
+            .saturating_mul(slack)
+            .checked_div(total_slack)
+            .unwrap();
+        distributed = distributed.saturating_add(share);
+        // unwrap is safe as the share is at most the slack, so the sum is at most width
+        widths.push(min.checked_add(usize::try_from(share).unwrap()).unwrap());
+    }
+
+    // rounding down leaves less than one column per column with slack
+    let mut leftover = available.saturating_sub(distributed);
+    for (width, &natural_width) in widths.iter_mut().zip(natural) {
+        if leftover == 0 {
+            break;
+        }
+        if *width < natural_width {
+            // unwrap is safe as width is less than natural_width
+            *width = width.checked_add(1).unwrap();
+            leftover = leftover.saturating_sub(1);
+        }
+    }
+    widths
+}
+
+/// Truncates each cell of a row to the width of its column, e.g. as computed by
+/// [`distribute_widths`]. Only available when the `std` feature of this library is activated, and
+/// it is activated by default.
+///
+/// Each cell is truncated like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate).
+/// Cells and widths are paired in order, and cells without a width are left out of the result.
+///
+/// # Arguments
+/// * `cells` - the cells of the row
+/// * `widths` - the widths of the columns
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::{distribute_widths, fit_row};
+///
+/// let cells = ["name", "你好吗"];
+/// let widths = distribute_widths(&[4, 6], &[4, 0], 8);
+/// assert_eq!(fit_row(&cells, &widths), [("name", 4), ("你好", 4)]);
+/// ```
+pub fn fit_row<'a>(cells: &'a [&'a str], widths: &[usize]) -> Vec<(&'a str, usize)> {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| cell.unicode_truncate(width))
+        .collect()
+}
+
+/// Pads each string to its width and joins them with `sep`, e.g. to render a table row. Only
+/// available when the `std` feature of this library is activated, and it is activated by default.
+///
+/// Each item is `(string, target_width, alignment)`, and the string is truncated and padded like
+/// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) with `truncate` set. So the result is
+/// exactly as wide as the target widths plus one separator between each two items.
+///
+/// # Arguments
+/// * `items` - the strings with their widths and alignments
+/// * `sep` - the separator between the padded strings
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::{unicode_pad_join, Alignment};
+///
+/// let row = [
+///     ("name", 6, Alignment::Left),
+///     ("你好吗", 5, Alignment::Left),
+///     ("42", 4, Alignment::Right),
+/// ];
+/// assert_eq!(unicode_pad_join(&row, " | "), "name   | 你好  |   42");
+/// ```
+pub fn unicode_pad_join(items: &[(&str, usize, Alignment)], sep: &str) -> String {
+    let mut result = String::new();
+    for (index, &(item, target_width, align)) in items.iter().enumerate() {
+        if index > 0 {
+            result.push_str(sep);
+        }
+        item.unicode_pad_into(target_width, align, true, ' ', &mut result);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn fits_unchanged() {
+        assert_eq!(distribute_widths(&[3, 5, 2], &[1, 1, 1], 10), [3, 5, 2]);
+        assert_eq!(distribute_widths(&[3, 5, 2], &[], 20), [3, 5, 2]);
+        assert!(distribute_widths(&[], &[], 0).is_empty());
+    }
+
+    #[test]
+    fn proportional() {
+        assert_eq!(distribute_widths(&[20, 40, 60], &[], 60), [10, 20, 30]);
+        assert_eq!(distribute_widths(&[20, 40, 60], &[], 0), [0, 0, 0]);
+    }
+
+    #[test]
+    fn leftover_left_to_right() {
+        // each column gets 3 1/3 columns, the leftover goes to the first one
+        assert_eq!(distribute_widths(&[10, 10, 10], &[], 10), [4, 3, 3]);
+        assert_eq!(distribute_widths(&[10, 10, 10], &[], 11), [4, 4, 3]);
+        // a column at its natural width does not take leftover columns
+        assert_eq!(distribute_widths(&[1, 10, 10], &[], 12), [1, 6, 5]);
+        for budget in 0..30 {
+            let widths = distribute_widths(&[7, 13, 9], &[2, 0, 3], budget);
+            let total: usize = widths.iter().sum();
+            assert_eq!(total, budget.clamp(5, 29), "{:?}", widths);
+        }
+    }
+
+    #[test]
+    fn minimums() {
+        assert_eq!(distribute_widths(&[10, 20], &[8, 0], 15), [9, 6]);
+        // a column already at its minimum is not shrunk
+        assert_eq!(distribute_widths(&[3, 20, 20], &[3, 0, 0], 13), [3, 5, 5]);
+        // a minimum above the natural width does not widen the column
+        assert_eq!(distribute_widths(&[2, 20], &[5, 0], 10), [2, 8]);
+    }
+
+    #[test]
+    fn minimums_over_budget() {
+        assert_eq!(distribute_widths(&[10, 20], &[8, 10], 15), [8, 10]);
+        assert_eq!(distribute_widths(&[10, 20], &[8, 30], 5), [8, 20]);
+    }
+
+    #[test]
+    fn huge_widths() {
+        let widths = distribute_widths(&[usize::MAX, usize::MAX], &[], usize::MAX);
+        assert_eq!(widths, [usize::MAX / 2 + 1, usize::MAX / 2]);
+    }
+
+    #[test]
+    fn fit_row_truncates() {
+        let cells = ["abcdef", "你好吗", "x"];
+        assert_eq!(
+            fit_row(&cells, &[3, 5, 0]),
+            [("abc", 3), ("你好", 4), ("", 0)]
+        );
+        assert_eq!(fit_row(&cells, &[3]), [("abc", 3)]);
+    }
+
+    #[test]
+    fn pad_join_total_width() {
+        let items = [
+            ("abcdef", 3, Alignment::Left),
+            ("你好吗", 5, Alignment::Center),
+            ("", 2, Alignment::Right),
+            ("y\u{0306}es", 4, Alignment::Right),
+        ];
+        for sep in ["", "|", "│ ", "你"] {
+            let row = unicode_pad_join(&items, sep);
+            assert_eq!(row.width(), 14 + 3 * sep.width(), "{:?}", row);
+        }
+        assert_eq!(unicode_pad_join(&items, "|"), "abc|你好 |  | y\u{0306}es");
+    }
+
+    #[test]
+    fn pad_join_no_separator_at_ends() {
+        assert_eq!(unicode_pad_join(&[], "|"), "");
+        assert_eq!(unicode_pad_join(&[("a", 2, Alignment::Right)], "|"), " a");
+    }
+}
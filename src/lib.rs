@@ -22,6 +22,38 @@
 //! use unicode_truncate::UnicodeTruncateStr;
 //! assert_eq!("你好吗".unicode_truncate(5), ("你好", 4));
 //! ```
+//!
+//! Characters can be removed from the start instead, or from both ends to keep the middle.
+//! ```rust
+//! use unicode_truncate::UnicodeTruncateStr;
+//! use unicode_width::UnicodeWidthStr;
+//!
+//! let (str, width) = "你好吗".unicode_truncate_start(5);
+//! assert_eq!((str, width), ("好吗", 4));
+//! assert_eq!(str.width(), width);
+//!
+//! let sentence = "The quick brown fox jumps over the lazy dog";
+//! let (str, width) = sentence.unicode_truncate_centered(11);
+//! assert_eq!((str, width), ("fox jumps o", 11));
+//! assert_eq!(str.width(), width);
+//! ```
+//!
+//! The side to remove characters from can also be chosen by alignment, keeping the side the
+//! string is aligned to.
+//! ```rust
+//! use unicode_truncate::{Alignment, UnicodeTruncateStr};
+//! use unicode_width::UnicodeWidthStr;
+//!
+//! for (align, expected) in [
+//!     (Alignment::Left, "abcd"),
+//!     (Alignment::Center, "bcde"),
+//!     (Alignment::Right, "cdef"),
+//! ] {
+//!     let (str, width) = "abcdef".unicode_truncate_aligned(4, align);
+//!     assert_eq!((str, width), (expected, 4));
+//!     assert_eq!(str.width(), width);
+//! }
+//! ```
 #![cfg_attr(
     feature = "std",
     doc = r##"
@@ -37,13 +69,111 @@ let str = "你好吗".unicode_pad(5, Alignment::Left, true);
 assert_eq!(str, "你好 ");
 assert_eq!(str.width(), 5);
 ```
+
+Padding follows the alignment, with an odd column of center padding going to the right.
+
+```rust
+use unicode_truncate::UnicodeTruncateStr;
+use unicode_truncate::Alignment;
+use unicode_width::UnicodeWidthStr;
+
+let str = "你好吗".unicode_pad(7, Alignment::Right, true);
+assert_eq!(str, " 你好吗");
+assert_eq!(str.width(), 7);
+
+let str = "你好吗".unicode_pad(9, Alignment::Center, true);
+assert_eq!(str, " 你好吗  ");
+assert_eq!(str.width(), 9);
+```
 "##
 )]
 
-use itertools::{merge_join_by, Either};
+use core::convert::TryFrom;
+use core::ops::Range;
+
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+#[cfg(feature = "arrayvec")]
+mod array;
+mod ascii;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod bidi;
+mod budget;
+#[cfg(feature = "cache")]
+mod cache;
+mod chunks;
+#[cfg(feature = "std")]
+mod fill;
+#[cfg(feature = "mismatch-hook")]
+mod hook;
+#[cfg(feature = "std")]
+mod layout;
+#[cfg(feature = "std")]
+mod list;
+mod model;
+#[cfg(feature = "normalization")]
+mod normalize;
+#[cfg(feature = "std")]
+mod os;
+mod outcome;
+#[cfg(feature = "debug")]
+mod ruler;
+mod scanner;
+mod smart;
+mod strict;
+#[cfg(feature = "std")]
+mod string;
+mod take;
+#[cfg(feature = "std")]
+mod try_pad;
+#[cfg(feature = "std")]
+mod wrap;
+mod zwj;
+
+#[cfg(feature = "arrayvec")]
+pub use array::PadArrayError;
+pub use ascii::const_ascii_width;
+#[cfg(feature = "rayon")]
+pub use batch::par_truncate_many;
+#[cfg(feature = "std")]
+pub use batch::truncate_many;
+pub use budget::WidthBudget;
+#[cfg(feature = "cache")]
+pub use cache::TruncateCache;
+pub use chunks::{Chunk, UnicodeChunks, UnicodeColumnChunks, UnicodeWrapHard, UnicodeWrapWords};
+#[cfg(feature = "std")]
+pub use fill::{CharFill, FillStrategy, PatternFill, SpaceFill};
+#[cfg(feature = "mismatch-hook")]
+pub use hook::{set_width_mismatch_hook, WidthMismatch};
+#[cfg(feature = "std")]
+pub use layout::{distribute_widths, fit_row, unicode_pad_join};
+#[cfg(feature = "std")]
+pub use list::truncate_list;
+#[cfg(feature = "compact")]
+pub use model::CompactWidthModel;
+pub use model::{MuslWcwidthModel, UnicodeWidthModel, WidthModel};
+#[cfg(feature = "normalization")]
+pub use normalize::NormalizationForm;
+#[cfg(feature = "std")]
+pub use os::{unicode_pad_os, unicode_truncate_os};
+pub use outcome::FitOutcome;
+pub use scanner::{ScanState, StartTruncateScanner};
+pub use smart::SmartTruncateConfig;
+pub use strict::UnknownWidthChar;
+#[cfg(feature = "std")]
+pub use string::UnicodeTruncateString;
+pub use take::{
+    take_while_fits, take_width, TakeWhileFits, TakeWidth, TruncateWidth, UnicodeTruncateIter,
+};
+#[cfg(feature = "std")]
+pub use try_pad::PadError;
+#[cfg(feature = "std")]
+pub use wrap::{unicode_hyphenate_wrap, unicode_wrap_words, BoxFit, UnicodeWrapIndented};
+pub use zwj::ZwjWidth;
+
 /// Defines the alignment for truncation and padding.
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum Alignment {
@@ -55,6 +185,27 @@ pub enum Alignment {
     Right,
 }
 
+impl Alignment {
+    /// Returns the alignment on the other side, e.g. when switching the text direction. Left and
+    /// right are swapped, while center is its own opposite.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::Alignment;
+    ///
+    /// assert_eq!(Alignment::Left.opposite(), Alignment::Right);
+    /// assert_eq!(Alignment::Center.opposite(), Alignment::Center);
+    /// ```
+    #[inline]
+    pub const fn opposite(self) -> Alignment {
+        match self {
+            Alignment::Left => Alignment::Right,
+            Alignment::Center => Alignment::Center,
+            Alignment::Right => Alignment::Left,
+        }
+    }
+}
+
 /// Methods for padding or truncating using displayed width of Unicode strings.
 pub trait UnicodeTruncateStr {
     /// Truncates a string to be at most `width` in terms of display width by removing the end
@@ -65,12 +216,46 @@ pub trait UnicodeTruncateStr {
     /// display width of the returned string slice is also returned.
     ///
     /// Zero-width characters decided by [`unicode_width`] are always included when deciding the
-    /// truncation point.
+    /// truncation point. So a string consisting entirely of zero-width characters is returned
+    /// unchanged, even for a `max_width` of 0.
+    ///
+    /// For the same reason, the length of the result in bytes has no bound derived from
+    /// `max_width`. Any grapheme can carry any number of combining marks, and a single emoji ZWJ
+    /// sequence like `"👨‍👩‍👧‍👦"` is 25 bytes in 2 columns. The only bound is the length of the string
+    /// itself, so a buffer for the result has to be sized from the input.
+    ///
+    /// Like other graphemes, a flag made of two regional indicators is never split, and it is 2
+    /// columns wide. A regional indicator without a partner, e.g. the last one of an odd run, is a
+    /// grapheme of its own and 1 column wide, as measured by [`unicode_width`]. All truncation
+    /// methods measure regional indicators this way.
     ///
     /// # Arguments
     /// * `max_width` - the maximum display width
+    #[must_use]
     fn unicode_truncate(&self, max_width: usize) -> (&str, usize);
 
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// and returns an owned copy of the result. Only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let (truncated, width) = "你好吗".unicode_truncate_owned(5);
+    /// assert_eq!((truncated, width), (String::from("你好"), 4));
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    fn unicode_truncate_owned(&self, max_width: usize) -> (String, usize) {
+        let (truncated, width) = self.unicode_truncate(max_width);
+        (truncated.to_owned(), width)
+    }
+
     /// Truncates a string to be at most `width` in terms of display width by removing the start
     /// characters.
     ///
@@ -78,13 +263,51 @@ pub trait UnicodeTruncateStr {
     /// the longest possible string is returned. To help the caller determine the situation, the
     /// display width of the returned string slice is also returned.
     ///
-    /// Zero-width characters decided by [`unicode_width`] are always removed when deciding the
-    /// truncation point.
+    /// Zero-width characters decided by [`unicode_width`] are removed if they are at the
+    /// beginning of the result after truncation. Likewise, if truncation leaves combining marks at
+    /// the start, e.g. because the control character they followed was removed, they are removed as
+    /// well. So a truncated result never begins with a character that would attach to whatever is
+    /// rendered before it. A string that is not truncated is returned unchanged though, including
+    /// a string consisting entirely of zero-width characters, even for a `max_width` of 0. Use
+    /// [`unicode_truncate_start_with_base`](crate::UnicodeTruncateStr::unicode_truncate_start_with_base)
+    /// to keep combining marks attached to a visible base instead.
     ///
     /// # Arguments
     /// * `max_width` - the maximum display width
+    #[must_use]
     fn unicode_truncate_start(&self, max_width: usize) -> (&str, usize);
 
+    /// Truncates a string like
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start), but keeps
+    /// combining marks left at the start by prepending `base`. Only available when the `std`
+    /// feature of this library is activated, and it is activated by default.
+    ///
+    /// Combining marks without a base are often rendered with a dotted circle, or attach to
+    /// whatever is displayed before the string. Prepending a visible base like a space makes them
+    /// render on their own column instead. The width of `base` counts towards `max_width`. If no
+    /// combining marks are left at the start, the result is the same as with
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start).
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `base` - the character to prepend to combining marks left at the start
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let input = "\u{1b}\u{0301}abc";
+    /// assert_eq!(input.unicode_truncate_start(3), ("abc", 3));
+    /// assert_eq!(input.unicode_truncate_start_with_base(4, ' '), (" \u{0301}abc".into(), 4));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_start_with_base(
+        &self,
+        max_width: usize,
+        base: char,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
     /// Truncates a string to be at most `width` in terms of display width by removing
     /// characters at both start and end.
     ///
@@ -92,13 +315,63 @@ pub trait UnicodeTruncateStr {
     /// the longest possible string is returned. To help the caller determine the situation, the
     /// display width of the returned string slice is also returned.
     ///
+    /// Characters are removed from both sides as evenly as possible, unless removing more from one
+    /// side keeps a wider string, e.g. when a wide character would be cut on the other side.
+    ///
     /// Zero-width characters decided by [`unicode_width`] are included if they are at end, or
-    /// removed if they are at the beginning when deciding the truncation point.
+    /// removed if they are at the beginning when deciding the truncation point. Combining marks
+    /// whose base would be removed are not kept at the beginning either, like in
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start). A string that
+    /// fits is never truncated though, so a string consisting entirely of zero-width characters is
+    /// returned unchanged, even for a `max_width` of 0.
     ///
     /// # Arguments
     /// * `max_width` - the maximum display width
+    #[must_use]
     fn unicode_truncate_centered(&self, max_width: usize) -> (&str, usize);
 
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// and also returns the length of the result in UTF-16 code units.
+    ///
+    /// The length is counted during the same scan, e.g. to slice the same string on the JavaScript
+    /// side of a WebAssembly binding, where strings are indexed by UTF-16 code units.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_utf16(5), ("你好", 4, 2));
+    /// assert_eq!("a😀b".unicode_truncate_utf16(3), ("a😀", 3, 3));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_utf16(&self, max_width: usize) -> (&str, usize, usize);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_centered`](crate::UnicodeTruncateStr::unicode_truncate_centered), and
+    /// also returns the grapheme at the display center of the result.
+    ///
+    /// The center grapheme is the one covering the middle column of the result, or the left one of
+    /// the two middle columns when the width of the result is even, e.g. for drawing a marker at the
+    /// center of a progress bar. Zero-width graphemes cover no column and are never the center.
+    /// When the result is 0 columns wide, the center grapheme is empty.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("abcdefg".unicode_truncate_centered_with_center(5), ("bcdef", 5, "d"));
+    /// assert_eq!("abcdefg".unicode_truncate_centered_with_center(4), ("bcde", 4, "c"));
+    /// assert_eq!("a你好b".unicode_truncate_centered_with_center(5), ("a你好", 5, "你"));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_centered_with_center(&self, max_width: usize) -> (&str, usize, &str);
+
     /// Truncates a string to be at most `width` in terms of display width by removing
     /// characters.
     ///
@@ -111,12 +384,42 @@ pub trait UnicodeTruncateStr {
     /// display width of the returned string slice is also returned.
     ///
     /// Zero-width characters decided by [`unicode_width`] are included if they are at end, or
-    /// removed if they are at the beginning when deciding the truncation point.
+    /// removed if they are at the beginning when deciding the truncation point. With any
+    /// alignment, a string consisting entirely of zero-width characters is returned unchanged.
     ///
     /// # Arguments
     /// * `max_width` - the maximum display width
     /// * `align` - alignment for truncation
+    ///
+    /// # Examples
+    /// Left alignment removes characters from the end. A wide character crossing the limit is
+    /// removed entirely, so the result may be narrower than requested.
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_aligned(5, Alignment::Left), ("你好", 4));
+    /// assert_eq!("abcdef".unicode_truncate_aligned(5, Alignment::Left), ("abcde", 5));
+    /// ```
+    ///
+    /// Right alignment removes characters from the start.
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_aligned(5, Alignment::Right), ("好吗", 4));
+    /// assert_eq!("abcdef".unicode_truncate_aligned(5, Alignment::Right), ("bcdef", 5));
+    /// ```
+    ///
+    /// Center alignment removes characters from both sides. As a wide character can not be split
+    /// into one column on each side, it is discarded from one side only.
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_aligned(4, Alignment::Center), ("你好", 4));
+    /// assert_eq!("你好吗呀".unicode_truncate_aligned(4, Alignment::Center), ("好吗", 4));
+    /// assert_eq!("abcdef".unicode_truncate_aligned(4, Alignment::Center), ("bcde", 4));
+    /// ```
     #[inline]
+    #[must_use]
     fn unicode_truncate_aligned(&self, max_width: usize, align: Alignment) -> (&str, usize) {
         match align {
             Alignment::Left => self.unicode_truncate(max_width),
@@ -125,480 +428,5623 @@ pub trait UnicodeTruncateStr {
         }
     }
 
-    /// Pads a string to be `width` in terms of display width. Only available when the `std` feature
-    /// of this library is activated, and it is activated by default.
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// but measures graphemes with the coarse [`CompactWidthModel`]. Only available when the
+    /// `compact` feature of this library is activated.
     ///
-    /// When `truncate` is true, the string is truncated to `width` if necessary. In case of wide
-    /// characters and truncation point not at character boundary, the longest possible string is
-    /// used, and padded to exact `width` according to `align`.
-    /// See [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) for the behavior of
-    /// truncation.
+    /// This trades accuracy for binary size: ASCII and CJK text is measured exactly, but other
+    /// characters are assumed to be 2 columns wide, so e.g. accented Latin text is truncated to
+    /// about half the width it could take. See [`CompactWidthModel`] for details.
     ///
     /// # Arguments
-    /// * `target_width` - the display width to pad to
-    /// * `align` - alignment for truncation and padding
-    /// * `truncate` - whether to truncate string if necessary
-    #[cfg(feature = "std")]
-    fn unicode_pad(
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_compact(5), ("你好", 4));
+    /// assert_eq!("héllo".unicode_truncate_compact(4), ("hél", 4));
+    /// ```
+    #[cfg(feature = "compact")]
+    #[must_use]
+    fn unicode_truncate_compact(&self, max_width: usize) -> (&str, usize);
+
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// and removes trailing whitespace from the result.
+    ///
+    /// This is useful before appending an indicator like an ellipsis, so that it is placed right
+    /// after the last visible character, e.g. `"hello…"` instead of `"hello   …"`. Whitespace is
+    /// decided by [`char::is_whitespace`], so wide whitespace like U+3000 IDEOGRAPHIC SPACE is
+    /// removed as well. The returned width is the width of the trimmed result.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("hello   world".unicode_truncate(8), ("hello   ", 8));
+    /// assert_eq!("hello   world".unicode_truncate_trim_trailing(8), ("hello", 5));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_trim_trailing(&self, max_width: usize) -> (&str, usize);
+
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// but fails if the result contains a character of unknown display width.
+    ///
+    /// [`UnicodeWidthChar::width`](unicode_width::UnicodeWidthChar::width) returns `None` for
+    /// control characters like `'\n'` or `'\u{7}'`, which
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) measures with a
+    /// substituted width. This lets a strict renderer refuse such characters instead of guessing
+    /// how a terminal displays them. Only the result is checked, so characters cut off by the
+    /// truncation are never reported.
+    ///
+    /// # Errors
+    /// Returns [`UnknownWidthChar`] for the first character in the result without a defined
+    /// width, reporting its byte offset.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_strict_width(5), Ok(("你好", 4)));
+    /// let err = "ab\u{7}c".unicode_truncate_strict_width(5).unwrap_err();
+    /// assert_eq!((err.offset(), err.character()), (2, '\u{7}'));
+    /// assert_eq!("ab\u{7}c".unicode_truncate_strict_width(2), Ok(("ab", 2)));
+    /// ```
+    fn unicode_truncate_strict_width(
         &self,
-        target_width: usize,
-        align: Alignment,
-        truncate: bool,
-    ) -> std::borrow::Cow<'_, str>;
-}
+        max_width: usize,
+    ) -> Result<(&str, usize), UnknownWidthChar>;
 
-impl UnicodeTruncateStr for str {
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// and also returns how the result relates to `max_width`.
+    ///
+    /// When the returned width is less than `max_width`, the [`FitOutcome`] tells whether the
+    /// string was that narrow to begin with, or whether a wide character at the truncation point
+    /// left columns unused, without measuring the string again.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{FitOutcome, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好".unicode_truncate_full(5), ("你好", 4, FitOutcome::Untouched));
+    /// assert_eq!("你好吗".unicode_truncate_full(4), ("你好", 4, FitOutcome::Exact));
+    /// assert_eq!(
+    ///     "你好吗".unicode_truncate_full(5),
+    ///     ("你好", 4, FitOutcome::Short { unusable_cols: 1 })
+    /// );
+    /// ```
+    #[must_use]
+    fn unicode_truncate_full(&self, max_width: usize) -> (&str, usize, FitOutcome);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start), and also
+    /// returns how the result relates to `max_width`, see [`FitOutcome`].
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{FitOutcome, UnicodeTruncateStr};
+    ///
+    /// assert_eq!(
+    ///     "你好吗".unicode_truncate_start_full(5),
+    ///     ("好吗", 4, FitOutcome::Short { unusable_cols: 1 })
+    /// );
+    /// ```
+    #[must_use]
+    fn unicode_truncate_start_full(&self, max_width: usize) -> (&str, usize, FitOutcome);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_centered`](crate::UnicodeTruncateStr::unicode_truncate_centered), and
+    /// also returns how the result relates to `max_width`, see [`FitOutcome`].
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{FitOutcome, UnicodeTruncateStr};
+    ///
+    /// assert_eq!(
+    ///     "你好吗".unicode_truncate_centered_full(3),
+    ///     ("好", 2, FitOutcome::Short { unusable_cols: 1 })
+    /// );
+    /// ```
+    #[must_use]
+    fn unicode_truncate_centered_full(&self, max_width: usize) -> (&str, usize, FitOutcome);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned), and also
+    /// returns how the result relates to `max_width`, see [`FitOutcome`].
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `align` - alignment for truncation
     #[inline]
-    fn unicode_truncate(&self, max_width: usize) -> (&str, usize) {
-        let (byte_index, new_width) = self
-            .grapheme_indices(true)
-            // map to byte index and the width of grapheme at the index
-            .map(|(byte_index, grapheme)| (byte_index, grapheme.width()))
-            // chain a final element representing the position past the last char
-            .chain(core::iter::once((self.len(), 0)))
-            // fold to byte index and the width up to the index
-            .scan(0, |sum: &mut usize, (byte_index, grapheme_width)| {
-                // byte_index is the start while the grapheme_width is at the end. Current width is
-                // the sum until now while the next byte_index is including the current
-                // grapheme_width.
-                let current_width = *sum;
-                *sum = sum.checked_add(grapheme_width)?;
-                Some((byte_index, current_width))
-            })
-            // take the longest but still shorter than requested
-            .take_while(|&(_, current_width)| current_width <= max_width)
-            .last()
-            .unwrap_or((0, 0));
-
-        // unwrap is safe as the index comes from grapheme_indices
-        let result = self.get(..byte_index).unwrap();
-        debug_assert_eq!(result.width(), new_width);
-        (result, new_width)
+    #[must_use]
+    fn unicode_truncate_aligned_full(
+        &self,
+        max_width: usize,
+        align: Alignment,
+    ) -> (&str, usize, FitOutcome) {
+        match align {
+            Alignment::Left => self.unicode_truncate_full(max_width),
+            Alignment::Center => self.unicode_truncate_centered_full(max_width),
+            Alignment::Right => self.unicode_truncate_start_full(max_width),
+        }
     }
 
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) to
+    /// a fraction of `total_columns`, e.g. a percentage of the terminal width.
+    ///
+    /// The maximum width is `total_columns * fraction` rounded down, clamped to
+    /// `0..=total_columns`. So a negative or NaN `fraction` truncates to an empty string, and a
+    /// `fraction` above 1 is treated as 1. The product is computed in `f32`, which is exact for
+    /// terminal sizes but may round for more than 2<sup>24</sup> columns.
+    ///
+    /// # Arguments
+    /// * `total_columns` - the width the fraction refers to
+    /// * `fraction` - the part of `total_columns` to truncate to
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_fraction(10, 0.5), ("你好", 4));
+    /// assert_eq!("abcdefgh".unicode_truncate_fraction(10, 0.333), ("abc", 3));
+    /// ```
     #[inline]
-    fn unicode_truncate_start(&self, max_width: usize) -> (&str, usize) {
-        let (byte_index, new_width) = self
-            .grapheme_indices(true)
-            // instead of start checking from the start do so from the end
-            .rev()
-            // map to byte index and the width of grapheme start at the index
-            .map(|(byte_index, grapheme)| (byte_index, grapheme.width()))
-            // fold to byte index and the width from end to the index
-            .scan(0, |sum: &mut usize, (byte_index, grapheme_width)| {
-                *sum = sum.checked_add(grapheme_width)?;
-                Some((byte_index, *sum))
-            })
-            .take_while(|&(_, current_width)| current_width <= max_width)
-            .last()
-            .unwrap_or((self.len(), 0));
-
-        // unwrap is safe as the index comes from grapheme_indices
-        let result = self.get(byte_index..).unwrap();
-        debug_assert_eq!(result.width(), new_width);
-        (result, new_width)
+    #[must_use]
+    fn unicode_truncate_fraction(&self, total_columns: usize, fraction: f32) -> (&str, usize) {
+        self.unicode_truncate(fraction_of_columns(total_columns, fraction))
     }
 
-    #[inline]
-    fn unicode_truncate_centered(&self, max_width: usize) -> (&str, usize) {
-        if max_width == 0 {
-            return ("", 0);
-        }
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate)
+    /// and returns the last grapheme of the result, or `None` if nothing fits.
+    ///
+    /// The grapheme is returned with its byte range in the original string, e.g. to position a
+    /// marker under the last visible character. As trailing zero-width characters are included in
+    /// truncation, the last grapheme may be zero-width.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_last_grapheme(5), Some((3..6, "好")));
+    /// assert_eq!("你好吗".unicode_truncate_last_grapheme(1), None);
+    /// ```
+    #[must_use]
+    fn unicode_truncate_last_grapheme(&self, max_width: usize) -> Option<(Range<usize>, &str)>;
 
-        let original_width = self.width();
-        if original_width <= max_width {
-            return (self, original_width);
-        }
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// but returns the byte range of the result in the original string instead of a slice.
+    ///
+    /// The range always starts at 0. Together with
+    /// [`unicode_truncate_start_range`](crate::UnicodeTruncateStr::unicode_truncate_start_range) and
+    /// [`unicode_truncate_centered_range`](crate::UnicodeTruncateStr::unicode_truncate_centered_range),
+    /// this allows mapping the truncation onto attributes stored alongside the string, e.g. styled
+    /// spans, without comparing pointers.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_range(5), (0..6, 4));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_range(&self, max_width: usize) -> (Range<usize>, usize);
 
-        // We need to remove at least this much
-        // unwrap is safe as original_width > max_width
-        let min_removal_width = original_width.checked_sub(max_width).unwrap();
+    /// Truncates a string like
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start), but returns
+    /// the byte range of the result in the original string instead of a slice.
+    ///
+    /// The range always ends at the length of the string.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_start_range(5), (3..9, 4));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_start_range(&self, max_width: usize) -> (Range<usize>, usize);
 
-        // Around the half to improve performance. In order to ensure the center grapheme stays
-        // remove its max possible length. This assumes a grapheme width is always <= 10 (4 people
-        // family emoji has width 8). This might end up not perfect on graphemes wider than this but
-        // performance is more important here.
-        let less_than_half = min_removal_width.saturating_sub(10) / 2;
+    /// Truncates a string like
+    /// [`unicode_truncate_centered`](crate::UnicodeTruncateStr::unicode_truncate_centered), but
+    /// returns the byte range of the result in the original string instead of a slice.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗呀".unicode_truncate_centered_range(4), (3..9, 4));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_centered_range(&self, max_width: usize) -> (Range<usize>, usize);
 
-        let from_start = self
-            .grapheme_indices(true)
-            .map(|(byte_index, grapheme)| (byte_index, grapheme.width()))
-            // fold to byte index and the width from start to the index (not including the current
-            // grapheme width)
-            .scan(
-                (0usize, 0usize),
-                |(sum, prev_width), (byte_index, grapheme_width)| {
-                    *sum = sum.checked_add(*prev_width)?;
-                    *prev_width = grapheme_width;
-                    Some((byte_index, *sum))
-                },
-            )
-            // fast forward to around the half
-            .skip_while(|&(_, removed)| removed < less_than_half);
-
-        let from_end = self
-            .grapheme_indices(true)
-            .map(|(byte_index, grapheme)| (byte_index, grapheme.width()))
-            .rev()
-            // fold to byte index and the width from end to the index (including the current
-            // grapheme width)
-            .scan(0usize, |sum, (byte_index, grapheme_width)| {
-                *sum = sum.checked_add(grapheme_width)?;
-                Some((byte_index, *sum))
-            })
-            // fast forward to around the half
-            .skip_while(|&(_, removed)| removed < less_than_half);
-
-        let (start_index, end_index, removed_width) = merge_join_by(
-            from_start,
-            from_end,
-            // taking from either left or right iter depending on which side has less removed width
-            |&(_, start_removed), &(_, end_removed)| start_removed < end_removed,
-        )
-        // remember the last left or right and combine them to one sequence of operations
-        .scan(
-            (0usize, 0usize, 0usize, 0usize),
-            |(start_removed, end_removed, start_index, end_index), position| {
-                match position {
-                    Either::Left((idx, removed)) => {
-                        *start_index = idx;
-                        *start_removed = removed;
-                    }
-                    Either::Right((idx, removed)) => {
-                        *end_index = idx;
-                        *end_removed = removed;
-                    }
-                }
-                // unwrap is safe as total length was also <= usize::MAX
-                let total_removed = start_removed.checked_add(*end_removed).unwrap();
-                Some((*start_index, *end_index, total_removed))
-            },
-        )
-        .find(|&(_, _, removed)| removed >= min_removal_width)
-        // should not happen as the removed width is not larger than the original width
-        // but a sane default is to remove everything (i.e. min_removal_width too large)
-        .unwrap_or((0, 0, original_width));
-
-        // unwrap is safe as the index comes from grapheme_indices
-        let result = self.get(start_index..end_index).unwrap();
-        // unwrap is safe as removed is always smaller than total width
-        let result_width = original_width.checked_sub(removed_width).unwrap();
-        debug_assert_eq!(result.width(), result_width);
-        (result, result_width)
-    }
+    /// Truncates a string to be at most `width` in terms of display width by removing characters
+    /// at both start and end, keeping the grapheme containing `pivot_byte` in the middle.
+    ///
+    /// This is useful to show the context around a position of interest, e.g. a search match. The
+    /// result starts from the grapheme containing `pivot_byte` and grows outward, adding graphemes
+    /// to the narrower side first, or after the pivot if both sides are equally wide. When the next
+    /// grapheme on one side does not fit, the other side keeps growing, so the longest possible
+    /// string is returned. If the pivot grapheme itself is wider than `max_width`, an empty string
+    /// is returned. A `pivot_byte` past the end of the string refers to the last grapheme.
+    ///
+    /// # Arguments
+    /// * `pivot_byte` - byte index of the position to keep visible
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let line = "error: unexpected token in expression";
+    /// let pivot = line.find("token").unwrap();
+    /// assert_eq!(line.unicode_truncate_around(pivot, 11), ("cted token ", 11));
+    /// assert_eq!("你好吗你好吗".unicode_truncate_around(6, 5), ("吗你", 4));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_around(&self, pivot_byte: usize, max_width: usize) -> (&str, usize);
 
-    #[cfg(feature = "std")]
-    #[inline]
-    fn unicode_pad(
+    /// Truncates a string like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned), but
+    /// measures emoji sequences according to `policy`.
+    ///
+    /// Terminals differ in whether they render ZWJ and modifier emoji sequences as one glyph. Use
+    /// this to match the terminal the text is displayed on. The returned width is measured under
+    /// the same policy. See [`ZwjWidth`](crate::ZwjWidth) for details.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `align` - alignment for truncation
+    /// * `policy` - how to measure emoji sequences
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr, ZwjWidth};
+    ///
+    /// let input = "a👨‍👩‍👧‍👦b";
+    /// assert_eq!(
+    ///     input.unicode_truncate_with_zwj_policy(4, Alignment::Left, ZwjWidth::Single),
+    ///     (input, 4)
+    /// );
+    /// assert_eq!(
+    ///     input.unicode_truncate_with_zwj_policy(4, Alignment::Left, ZwjWidth::Sum),
+    ///     ("a", 1)
+    /// );
+    /// ```
+    #[must_use]
+    fn unicode_truncate_with_zwj_policy(
         &self,
-        target_width: usize,
+        max_width: usize,
         align: Alignment,
-        truncate: bool,
-    ) -> std::borrow::Cow<'_, str> {
-        use std::borrow::Cow;
-
-        if !truncate && self.width() >= target_width {
-            return Cow::Borrowed(self);
-        }
+        policy: ZwjWidth,
+    ) -> (&str, usize);
 
-        let (truncated, columns) = self.unicode_truncate(target_width);
-        if columns == target_width {
-            return Cow::Borrowed(truncated);
-        }
+    /// Truncates a string to be at most `width` in terms of display width by removing the end
+    /// characters, preferring to cut at a word boundary.
+    ///
+    /// When `config.prefer_word_boundary` is set, the string is cut after the last whole word that
+    /// fits, and trailing whitespace is dropped. If that leaves less than
+    /// `config.min_acceptable_fraction` of `max_width` filled, e.g. because of a long word, the
+    /// result of [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) is returned
+    /// instead. A string that fits is returned as is.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `config` - when to prefer word boundaries
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{SmartTruncateConfig, UnicodeTruncateStr};
+    ///
+    /// let config = SmartTruncateConfig::default();
+    /// assert_eq!("hello wonderful world".unicode_truncate_smart(18, config), ("hello wonderful", 15));
+    /// // "hello" fills less than 75% of the width
+    /// assert_eq!("hello wonderful world".unicode_truncate_smart(12, config), ("hello wonder", 12));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_smart(
+        &self,
+        max_width: usize,
+        config: SmartTruncateConfig,
+    ) -> (&str, usize);
 
-        // the string is less than width, or truncated to less than width
-        let diff = target_width.saturating_sub(columns);
-        let (left_pad, right_pad) = match align {
-            Alignment::Left => (0, diff),
-            Alignment::Right => (diff, 0),
-            Alignment::Center => (diff / 2, diff.saturating_sub(diff / 2)),
-        };
-        debug_assert_eq!(diff, left_pad.saturating_add(right_pad));
+    /// Truncates a string to the longest prefix that fits in `max_width` in terms of display width
+    /// and ends right before one of `breakpoints`.
+    ///
+    /// This keeps segments of structured text whole, e.g. the components of a path with `'/'` or
+    /// the fields of a time with `':'`. The breakpoint character itself is not included, and the
+    /// whole string is returned if it fits. If no breakpoint leaves a prefix that fits, the result
+    /// is empty, or with `fallback` set, the string is truncated like
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate). Breakpoints are only
+    /// matched at the start of a grapheme, so they never split one.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `breakpoints` - the characters to truncate before
+    /// * `fallback` - whether to truncate at the exact width if no breakpoint fits
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("/usr/share/doc".unicode_truncate_at(12, &['/'], false), ("/usr/share", 10));
+    /// assert_eq!("12:34:56".unicode_truncate_at(7, &[':'], false), ("12:34", 5));
+    /// assert_eq!("12:34:56".unicode_truncate_at(1, &[':'], false), ("", 0));
+    /// assert_eq!("12:34:56".unicode_truncate_at(1, &[':'], true), ("1", 1));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_at(
+        &self,
+        max_width: usize,
+        breakpoints: &[char],
+        fallback: bool,
+    ) -> (&str, usize);
 
-        let new_len = truncated
-            .len()
-            .checked_add(diff)
-            .expect("Padded result should fit in a new String");
-        let mut result = String::with_capacity(new_len);
-        for _ in 0..left_pad {
-            result.push(' ');
-        }
-        result += truncated;
-        for _ in 0..right_pad {
-            result.push(' ');
-        }
-        Cow::Owned(result)
-    }
-}
+    /// Splits a string into consecutive chunks, each at most `max_width` in terms of display width.
+    ///
+    /// Each chunk is the longest prefix of the remaining string that fits, as returned by
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), together with its
+    /// display width. The chunks concatenate to the whole string, unless a grapheme wider than
+    /// `max_width` is reached, in which case the iteration stops there. Nothing is yielded for a
+    /// `max_width` of 0.
+    ///
+    /// The iterator does not allocate and is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width of each chunk
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let mut chunks = "你好吗abc".unicode_chunks(3);
+    /// assert_eq!(chunks.next(), Some(("你", 2)));
+    /// assert_eq!(chunks.next(), Some(("好", 2)));
+    /// assert_eq!(chunks.next(), Some(("吗a", 3)));
+    /// assert_eq!(chunks.next(), Some(("bc", 2)));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    #[must_use]
+    fn unicode_chunks(&self, max_width: usize) -> UnicodeChunks<'_>;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Splits a string into consecutive chunks like
+    /// [`unicode_chunks`](crate::UnicodeTruncateStr::unicode_chunks), also reporting the column
+    /// each chunk starts at.
+    ///
+    /// This is useful for fixed-column displays like a marquee, where a chunk shorter than
+    /// `max_width`, because it is the last one or a wide character did not fit, has to be padded.
+    /// The [`start_col`](Chunk::start_col) of a chunk is the total width of the chunks before it.
+    /// Like for [`unicode_chunks`](crate::UnicodeTruncateStr::unicode_chunks), each chunk is at
+    /// most `max_width` wide, the iteration stops at a grapheme wider than `max_width`, and nothing
+    /// is yielded for a `max_width` of 0.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width of each chunk
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Chunk, UnicodeTruncateStr};
+    ///
+    /// let mut chunks = "你好吗".unicode_column_chunks(3);
+    /// assert_eq!(chunks.next(), Some(Chunk { text: "你", width: 2, start_col: 0 }));
+    /// assert_eq!(chunks.next(), Some(Chunk { text: "好", width: 2, start_col: 2 }));
+    /// assert_eq!(chunks.next(), Some(Chunk { text: "吗", width: 2, start_col: 4 }));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    #[must_use]
+    fn unicode_column_chunks(&self, max_width: usize) -> UnicodeColumnChunks<'_>;
 
-    mod truncate_end {
-        use super::*;
+    /// Wraps a string into lines of at most `max_width` in terms of display width, without regard
+    /// to words.
+    ///
+    /// Each line is the longest prefix of the remaining string that fits, as returned by
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), together with its
+    /// display width, so graphemes are never split. A grapheme wider than `max_width` is placed on
+    /// a line of its own, which is then wider than `max_width`. So every line makes progress, and
+    /// the lines concatenate to the whole string.
+    ///
+    /// The iterator does not allocate and is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width of each line
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let mut lines = "ab你c".unicode_wrap_hard(1);
+    /// assert_eq!(lines.next(), Some(("a", 1)));
+    /// assert_eq!(lines.next(), Some(("b", 1)));
+    /// assert_eq!(lines.next(), Some(("你", 2)));
+    /// assert_eq!(lines.next(), Some(("c", 1)));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    #[must_use]
+    fn unicode_wrap_hard(&self, max_width: usize) -> UnicodeWrapHard<'_>;
+
+    /// Wraps a string into lines of at most `max_width` in terms of display width, breaking at
+    /// word boundaries.
+    ///
+    /// Lines are filled greedily with words as segmented by Unicode word boundaries, so text
+    /// without spaces like CJK can be broken between any two ideographs. Whitespace at the breaks
+    /// is trimmed, and a newline always ends a line, so a blank line is yielded as an empty line.
+    /// A word wider than `max_width` is broken between graphemes like
+    /// [`unicode_wrap_hard`](crate::UnicodeTruncateStr::unicode_wrap_hard), and a grapheme wider
+    /// than `max_width` is put on a line by itself.
+    ///
+    /// Lines are measured exactly like
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), so a line fits in a cell
+    /// truncated to the same width, as with [`unicode_wrap_words`](crate::unicode_wrap_words).
+    /// Unlike that function, single newlines are not joined, and the iterator borrows every line
+    /// from the string without allocating, so it is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width of each line
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let text = "The quick brown fox\n\n你好吗";
+    /// let lines: Vec<_> = text.unicode_wrap_words(10).collect();
+    /// assert_eq!(lines, ["The quick", "brown fox", "", "你好吗"]);
+    /// ```
+    #[must_use]
+    fn unicode_wrap_words(&self, max_width: usize) -> UnicodeWrapWords<'_>;
+
+    /// Flows a string into up to `num_columns` pieces of at most `column_width` in terms of display
+    /// width, e.g. for a newspaper or grid layout with one line per column. Only available when
+    /// the `std` feature of this library is activated, and it is activated by default.
+    ///
+    /// Each piece is filled with as many words as fit, breaking at Unicode word boundaries like
+    /// [`unicode_wrap_words`](crate::unicode_wrap_words), so text without spaces like CJK can be
+    /// broken between any two ideographs. Whitespace at the breaks is removed, and a newline always
+    /// starts the next piece, while blank lines are skipped. A word wider than `column_width` is
+    /// broken between graphemes, and a grapheme wider than `column_width` is put in a piece by
+    /// itself. Fewer pieces are returned if the text runs out before `num_columns` are filled. All
+    /// pieces borrow from the string.
+    ///
+    /// # Arguments
+    /// * `column_width` - the maximum display width of each piece
+    /// * `num_columns` - the maximum number of pieces
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let text = "The quick brown fox jumps.";
+    /// assert_eq!(
+    ///     text.unicode_flow_columns(10, 2),
+    ///     [("The quick", 9), ("brown fox", 9)]
+    /// );
+    /// assert_eq!(text.unicode_flow_columns(20, 3), [("The quick brown fox", 19), ("jumps.", 6)]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_flow_columns(&self, column_width: usize, num_columns: usize) -> Vec<(&str, usize)>;
+
+    /// Wraps a string like [`unicode_wrap_words`](crate::UnicodeTruncateStr::unicode_wrap_words),
+    /// indenting every line after the first with `continuation_prefix`. Only available when the
+    /// `std` feature of this library is activated, and it is activated by default.
+    ///
+    /// The first line is wrapped to `first_width` columns and borrows from the string. Each
+    /// following line is wrapped to the columns of `total_width` left after the prefix, measured
+    /// like the lines themselves, so a prefix with wide characters never pushes a line past
+    /// `total_width`. A prefix at least `total_width` wide leaves no columns, so every following
+    /// line holds a single grapheme. The prefix is added to every following line, including the
+    /// empty lines of blank lines in the string.
+    ///
+    /// # Arguments
+    /// * `first_width` - the maximum display width of the first line
+    /// * `continuation_prefix` - the prefix of every following line
+    /// * `total_width` - the maximum display width of every following line, including the prefix
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let text = "The quick brown fox jumps.";
+    /// let lines: Vec<_> = text.unicode_wrap_indented(10, "  ↳ ", 14).collect();
+    /// assert_eq!(lines, ["The quick", "  ↳ brown fox", "  ↳ jumps."]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_wrap_indented<'a>(
+        &'a self,
+        first_width: usize,
+        continuation_prefix: &'a str,
+        total_width: usize,
+    ) -> UnicodeWrapIndented<'a>;
+
+    /// Wraps a string like [`unicode_wrap_words`](crate::UnicodeTruncateStr::unicode_wrap_words)
+    /// and keeps at most `max_lines` lines, ending the last one with `ellipsis` if any text is left
+    /// out. Only available when the `std` feature of this library is activated, and it is activated
+    /// by default.
+    ///
+    /// Newlines in the string always start a new line, so they count toward `max_lines`. When text
+    /// is left out, the break of the last line is chosen with the columns of the ellipsis taken
+    /// already, so the last line is never wider than `max_width` either. An ellipsis wider than
+    /// `max_width` is truncated itself. The returned flag tells whether any text was left out. The
+    /// lines borrow from the string, except for a last line with the ellipsis.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width of each line, including the ellipsis
+    /// * `max_lines` - the maximum number of lines
+    /// * `ellipsis` - the string to end the last line with when text is left out
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let text = "The quick brown fox jumps.";
+    /// let (lines, clamped) = text.unicode_clamp_lines(10, 2, "...");
+    /// assert_eq!(lines, ["The quick", "brown..."]);
+    /// assert!(clamped);
+    /// let (lines, clamped) = text.unicode_clamp_lines(20, 2, "...");
+    /// assert_eq!(lines, ["The quick brown fox", "jumps."]);
+    /// assert!(!clamped);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_clamp_lines(
+        &self,
+        max_width: usize,
+        max_lines: usize,
+        ellipsis: &str,
+    ) -> (Vec<std::borrow::Cow<'_, str>>, bool);
+
+    /// Fits a string into a box of `height` lines of exactly `width` columns. Only available when
+    /// the `std` feature of this library is activated, and it is activated by default.
+    ///
+    /// The string is wrapped like
+    /// [`unicode_wrap_words`](crate::UnicodeTruncateStr::unicode_wrap_words), so newlines in the
+    /// string start new lines, and only the first `height` lines are kept. Each line is padded to
+    /// `width` according to `align`, including lines with a column left unused by a wide
+    /// character. A grapheme wider than `width` does not fit on any line and is replaced with
+    /// padding. [`BoxFit::truncated`] tells whether any text was left out, either lines beyond
+    /// `height` or such a grapheme, e.g. to draw a scroll indicator, and [`BoxFit::used_height`]
+    /// how many lines hold text.
+    ///
+    /// An empty string has no lines. With `fill_to_height`, blank lines of `width` columns are
+    /// added until there are `height` lines.
+    ///
+    /// # Arguments
+    /// * `width` - the display width of each line
+    /// * `height` - the maximum number of lines
+    /// * `align` - alignment for padding
+    /// * `fill_to_height` - whether to add blank lines to fill the box
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let fit = "The quick brown fox jumps.".unicode_fit_box(10, 2, Alignment::Left, false);
+    /// assert_eq!(fit.lines, ["The quick ", "brown fox "]);
+    /// assert!(fit.truncated);
+    ///
+    /// let fit = "你好吗".unicode_fit_box(5, 3, Alignment::Center, true);
+    /// assert_eq!(fit.lines, ["你好 ", " 吗  ", "     "]);
+    /// assert!(!fit.truncated);
+    /// assert_eq!(fit.used_height, 2);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_fit_box(
+        &self,
+        width: usize,
+        height: usize,
+        align: Alignment,
+        fill_to_height: bool,
+    ) -> BoxFit;
+
+    /// Truncates a string like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned), but
+    /// measures graphemes with `model`.
+    ///
+    /// This allows matching the width calculation of a specific terminal, see [`WidthModel`]. The
+    /// returned width is measured with `model` as well.
+    ///
+    /// The widths returned by `model` are summed with saturation at `usize::MAX`, so even huge
+    /// widths never wrap around. A string wider than `max_width` in total is truncated exactly,
+    /// while a result wider than `usize::MAX` is reported as `usize::MAX` wide, which only happens
+    /// when `max_width` is `usize::MAX`.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `align` - alignment for truncation
+    /// * `model` - the width model to measure graphemes with
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, MuslWcwidthModel, UnicodeTruncateStr};
+    ///
+    /// let input = "a\u{2764}\u{FE0F}b";
+    /// assert_eq!(input.unicode_truncate_aligned(3, Alignment::Left), ("a\u{2764}\u{FE0F}", 3));
+    /// assert_eq!(
+    ///     input.unicode_truncate_with_model(3, Alignment::Left, &MuslWcwidthModel),
+    ///     (input, 3)
+    /// );
+    /// ```
+    #[must_use]
+    fn unicode_truncate_with_model(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        model: &dyn WidthModel,
+    ) -> (&str, usize);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned), while
+    /// keeping bidi directional formatting characters balanced. Only available when the `std`
+    /// feature of this library is activated, and it is activated by default.
+    ///
+    /// Truncation may remove the terminator (PDI U+2069 or PDF U+202C) of an isolate or embedding
+    /// opened in the kept part, which leaves it open and scrambles anything rendered after the
+    /// result in a bidi-aware context. The missing terminators are appended to the result. Likewise,
+    /// terminators whose opener was removed, e.g. by truncating the start, are dropped. The
+    /// directional formatting characters are zero-width, so the returned width is the same as the
+    /// one returned by `unicode_truncate_aligned`. A string that is not truncated is returned as
+    /// is, even if its directional formatting characters are unbalanced.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `align` - alignment for truncation
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_bidi_safe(
+        &self,
+        max_width: usize,
+        align: Alignment,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
+    /// Truncates a string to be at most `width` in terms of display width, marking the removed
+    /// part with `ellipsis`. Only available when the `std` feature of this library is activated,
+    /// and it is activated by default.
+    ///
+    /// If the string fits, it is returned as is. Otherwise the ellipsis is placed on the side
+    /// characters were removed from, which depends on the alignment. When left aligned, the end is
+    /// removed and the ellipsis is appended. When right aligned, the start is removed and the
+    /// ellipsis is prepended. When centered, the middle is removed and replaced by the ellipsis,
+    /// keeping both ends. The ellipsis is truncated itself if `max_width` is too small for it. A
+    /// dangling ZWJ at the end of the kept start is removed, so it does not join the ellipsis or
+    /// the kept end into the last grapheme.
+    ///
+    /// The returned width is the display width of the whole result, including the ellipsis when
+    /// one was added, so it can be used directly to position whatever follows.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width, including the ellipsis
+    /// * `align` - alignment for truncation
+    /// * `ellipsis` - the string to mark the removed part with
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let input = "你好吗你好吗";
+    /// assert_eq!(input.unicode_truncate_ellipsis(7, Alignment::Left, "…"), ("你好吗…".into(), 7));
+    /// assert_eq!(input.unicode_truncate_ellipsis(7, Alignment::Right, "…"), ("…你好吗".into(), 7));
+    /// assert_eq!(input.unicode_truncate_ellipsis(7, Alignment::Center, "…"), ("你…好吗".into(), 7));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_ellipsis(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        ellipsis: &str,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
+    /// Truncates a string like
+    /// [`unicode_truncate_ellipsis`](crate::UnicodeTruncateStr::unicode_truncate_ellipsis), but
+    /// keeps at least `min_content_graphemes` graphemes of the string next to the ellipsis. Only
+    /// available when the `std` feature of this library is activated, and it is activated by
+    /// default.
+    ///
+    /// This avoids results that are just the ellipsis when `max_width` is small. If the regular
+    /// result keeps enough graphemes, it is returned unchanged. Otherwise the first
+    /// `min_content_graphemes` graphemes are kept when left aligned, the last ones when right
+    /// aligned, and both ends when centered, with an extra grapheme of an odd count at the end.
+    /// The ellipsis is always added when anything was removed, so **the result is then wider than
+    /// `max_width`**, and the returned width tells by how much. If the string does not have more
+    /// graphemes than `min_content_graphemes`, it is returned as is.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width, including the ellipsis, unless it is exceeded to
+    ///   keep enough graphemes
+    /// * `align` - alignment for truncation
+    /// * `ellipsis` - the string to mark the removed part with
+    /// * `min_content_graphemes` - the least number of graphemes of the string to keep
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let input = "你好吗你好吗";
+    /// let truncate = |max_width, align| {
+    ///     input.unicode_truncate_ellipsis_min_content(max_width, align, "…", 1)
+    /// };
+    /// assert_eq!(truncate(5, Alignment::Left), ("你好…".into(), 5));
+    /// assert_eq!(truncate(2, Alignment::Left), ("你…".into(), 3));
+    /// assert_eq!(truncate(2, Alignment::Right), ("…吗".into(), 3));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_ellipsis_min_content(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        ellipsis: &str,
+        min_content_graphemes: usize,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
+    /// Truncates a string to be at most `max_width` in terms of display width, keeping its first
+    /// `prefix_width` columns intact and marking the removed part of the rest with `ellipsis`. Only
+    /// available when the `std` feature of this library is activated, and it is activated by
+    /// default.
+    ///
+    /// This is for lines like `"ERROR: <message>"`, where the label should always be shown and
+    /// only the value is truncated. The prefix is the longest start of the string that fits in
+    /// `prefix_width`, like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate)
+    /// returns it, and the rest is truncated like
+    /// [`unicode_truncate_ellipsis`](crate::UnicodeTruncateStr::unicode_truncate_ellipsis) with
+    /// left alignment to the columns the prefix leaves. If the string fits, it is returned as is.
+    /// If `prefix_width` is at least `max_width`, there is no room for the value, and the whole
+    /// string is truncated with the ellipsis instead, prefix included.
+    ///
+    /// # Arguments
+    /// * `prefix_width` - the display width of the prefix to keep
+    /// * `max_width` - the maximum display width, including the ellipsis
+    /// * `ellipsis` - the string to mark the removed part with
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let line = "ERROR: disk full";
+    /// assert_eq!(line.unicode_truncate_after_prefix(7, 12, "…"), ("ERROR: disk…".into(), 12));
+    /// assert_eq!(line.unicode_truncate_after_prefix(7, 20, "…"), (line.into(), 16));
+    /// assert_eq!(line.unicode_truncate_after_prefix(7, 4, "…"), ("ERR…".into(), 4));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_after_prefix(
+        &self,
+        prefix_width: usize,
+        max_width: usize,
+        ellipsis: &str,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
+    /// Fits a string to be exactly `width` in terms of display width, by truncating with an
+    /// ellipsis and padding with spaces. Only available when the `std` feature of this library is
+    /// activated, and it is activated by default.
+    ///
+    /// See [`unicode_truncate_ellipsis`](crate::UnicodeTruncateStr::unicode_truncate_ellipsis) for
+    /// where the ellipsis is placed depending on the alignment. Padding is added according to the
+    /// alignment as well, including when a wide character prevents reaching `width` exactly.
+    ///
+    /// # Arguments
+    /// * `width` - the display width to fit to
+    /// * `align` - alignment for truncation and padding
+    /// * `ellipsis` - the string to mark the removed part with
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好吗你好吗".unicode_fit(6, Alignment::Left, "…"), "你好… ");
+    /// assert_eq!("你好".unicode_fit(6, Alignment::Right, "…"), "  你好");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_fit(
+        &self,
+        width: usize,
+        align: Alignment,
+        ellipsis: &str,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Pads a string to be `width` in terms of display width, with a separator between the content
+    /// and the padding. Only available when the `std` feature of this library is activated, and it
+    /// is activated by default.
+    ///
+    /// The string is truncated to `width` if necessary, like
+    /// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) with `truncate` set. When padding
+    /// is needed, `sep` takes the column of padding next to the content, and `fill` is used for the
+    /// rest. With center alignment, each side with padding gets a separator. A `sep` wider than the
+    /// padding on a side is left out there, and the columns a wide `fill` can not cover are filled
+    /// with spaces, so the result is never wider than `target_width`.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `fill` - the character to pad with
+    /// * `sep` - the separator between the content and the padding
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("42".unicode_pad_sep(6, Alignment::Right, '.', '|'), "...|42");
+    /// assert_eq!("42".unicode_pad_sep(6, Alignment::Center, '.', '|'), ".|42|.");
+    /// assert_eq!("42".unicode_pad_sep(2, Alignment::Right, '.', '|'), "42");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_sep(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        fill: char,
+        sep: char,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Truncates a string to `target_width` in terms of display width, and pads it with `fill`
+    /// only if one is given. Only available when the `std` feature of this library is activated,
+    /// and it is activated by default.
+    ///
+    /// The string is truncated like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned) with
+    /// `align`. With `fill` set to `None`, the truncated string is returned as is, borrowed. With
+    /// `Some`, it is padded to `target_width` with the fill character according to `align`, and
+    /// columns a wide fill can not cover are filled with spaces. So the content is the same either
+    /// way, and only the padding is conditional.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `fill` - the character to pad with, or `None` for no padding
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好吗".unicode_pad_opt_fill(5, Alignment::Right, None), "好吗");
+    /// assert_eq!("你好吗".unicode_pad_opt_fill(5, Alignment::Right, Some('.')), ".好吗");
+    /// assert_eq!("42".unicode_pad_opt_fill(4, Alignment::Left, Some('.')), "42..");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_opt_fill(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        fill: Option<char>,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Right-aligns a string in `target_width` columns, leaving `right_gutter` columns of padding
+    /// after it. Only available when the `std` feature of this library is activated, and it is
+    /// activated by default.
+    ///
+    /// The string is right-aligned like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad)
+    /// within the columns left of the gutter, and the gutter is filled with spaces and counted in
+    /// `target_width`, e.g. for numeric columns that should not touch the border on the right. When
+    /// `truncate` is true, the string is truncated to the columns left of the gutter if necessary.
+    /// Otherwise a string wider than those columns is returned as is, without a gutter. A gutter of
+    /// at least `target_width` leaves no columns for the string.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to, including the gutter
+    /// * `right_gutter` - the display width of the padding to keep after the string
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("42".unicode_pad_right_gutter(6, 1, true), "   42 ");
+    /// assert_eq!("12345".unicode_pad_right_gutter(6, 1, true), "12345 ");
+    /// assert_eq!("123456".unicode_pad_right_gutter(6, 1, true), "12345 ");
+    /// assert_eq!("123456".unicode_pad_right_gutter(6, 1, false), "123456");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_right_gutter(
+        &self,
+        target_width: usize,
+        right_gutter: usize,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Pads a string to be `width` in terms of display width, with different fill characters on
+    /// the left and the right. Only available when the `std` feature of this library is activated,
+    /// and it is activated by default.
+    ///
+    /// Truncation and the split of the padding are the same as for
+    /// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad). The padding on the left consists of
+    /// `left_fill`, and the padding on the right of `right_fill`.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    /// * `left_fill` - the character to pad the left side with
+    /// * `right_fill` - the character to pad the right side with
+    ///
+    /// # Panics
+    /// Panics if `left_fill` or `right_fill` is not exactly 1 column wide, or if the padded string
+    /// is too long like for [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let padded = "你好".unicode_pad_asymmetric_fill(9, Alignment::Center, true, '>', '<');
+    /// assert_eq!(padded, ">>你好<<<");
+    /// let padded = "你好吗".unicode_pad_asymmetric_fill(5, Alignment::Left, true, '>', '<');
+    /// assert_eq!(padded, "你好<");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_asymmetric_fill(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        left_fill: char,
+        right_fill: char,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Pads a string to be `width` in terms of display width, with fill characters decided by
+    /// `fill` at runtime. Only available when the `std` feature of this library is activated, and
+    /// it is activated by default.
+    ///
+    /// Truncation and the split of the padding are the same as for
+    /// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad). See [`FillStrategy`] for how the
+    /// padding is filled, e.g. from a fill pattern configured by the user.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    /// * `fill` - the strategy deciding the fill characters
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, CharFill, PatternFill, SpaceFill, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("42".unicode_pad_strategy(5, Alignment::Right, true, &SpaceFill), "   42");
+    /// assert_eq!("42".unicode_pad_strategy(5, Alignment::Right, true, &CharFill('0')), "00042");
+    /// let leader = PatternFill(". ");
+    /// assert_eq!("ab".unicode_pad_strategy(7, Alignment::Left, true, &leader), "ab. . .");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_strategy(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        fill: &dyn FillStrategy,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Truncates a string to leave room for a trailing caret, and appends the caret. Only available
+    /// when the `std` feature of this library is activated, and it is activated by default.
+    ///
+    /// The string is truncated like
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) to `max_width` minus the
+    /// width of `caret`. If the string had to be truncated and a wide character at the truncation
+    /// point leaves a gap, the gap is filled with spaces before the caret, so the result is always
+    /// exactly `max_width` columns when the string is long enough. If `max_width` is less than the
+    /// width of the caret, an empty string is returned. The returned width includes the caret and
+    /// any filled gap. A dangling ZWJ at the end of the kept string is removed, so it does not join
+    /// the caret into the last grapheme.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width, including the caret
+    /// * `caret` - the caret to append
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("abc".unicode_truncate_with_caret(5, '█'), ("abc█".into(), 4));
+    /// assert_eq!("abcdef".unicode_truncate_with_caret(5, '█'), ("abcd█".into(), 5));
+    /// assert_eq!("你好吗".unicode_truncate_with_caret(4, '█'), ("你 █".into(), 4));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_with_caret(
+        &self,
+        max_width: usize,
+        caret: char,
+    ) -> (std::borrow::Cow<'_, str>, usize);
+
+    /// Truncates a string to be at most `width` in terms of display width by removing the end
+    /// units, where units are defined by `segmenter` instead of graphemes.
+    ///
+    /// This is useful when some logical units must not be split, e.g. markup like
+    /// `[color]...[/color]` that should be kept or removed as a whole. Like graphemes in
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), the display width of each
+    /// unit is counted as a whole, and zero-width units are always included when deciding the
+    /// truncation point.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `segmenter` - splits the string into atomic units, returning the byte index and the slice
+    ///   of each unit in order. The units should together cover the whole string.
+    ///
+    /// # Panics
+    /// Panics if a byte index returned by `segmenter` is not at a char boundary of the string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_segmentation::UnicodeSegmentation;
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// // treat each word as a unit
+    /// let words = |s| UnicodeSegmentation::split_word_bound_indices(s);
+    /// assert_eq!("hello world".unicode_truncate_units(8, words), ("hello ", 6));
+    /// ```
+    #[must_use]
+    fn unicode_truncate_units<'a, F, I>(
+        &'a self,
+        max_width: usize,
+        segmenter: F,
+    ) -> (&'a str, usize)
+    where
+        F: FnOnce(&'a str) -> I,
+        I: IntoIterator<Item = (usize, &'a str)>;
+
+    /// Renders the column grid of a string, showing which columns each grapheme occupies. Only
+    /// available when the `debug` feature of this library is activated.
+    ///
+    /// This is a developer aid for diagnosing width issues and reporting truncation bugs. The
+    /// result has multiple lines: a column ruler (with a line of tens for strings wider than 10
+    /// columns), the string itself with control characters replaced by U+FFFD, and a line marking
+    /// the cells of each grapheme, `^` for narrow and `<>` for wide ones. Zero-width graphemes are
+    /// listed after that, one per line, with the column they are at and their code points.
+    ///
+    /// The exact format is meant for humans and may change.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!(
+    ///     "a你\u{200B}b".unicode_width_ruler(),
+    ///     "0123\na你\u{200B}b\n^<>^\ncolumn 3: zero-width U+200B"
+    /// );
+    /// ```
+    #[cfg(feature = "debug")]
+    #[must_use]
+    fn unicode_width_ruler(&self) -> String;
+
+    /// Truncates a string like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate),
+    /// after replacing characters with `remap`. Only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// `remap` is called for each character and returns the replacement, or `None` to keep the
+    /// character. The width is measured on the remapped graphemes, which are also what is
+    /// returned, and the characters after the first grapheme not fitting are not remapped.
+    /// This is useful for presentation-layer shaping, e.g. displaying ASCII digits as full-width
+    /// digits. If no character is replaced, the result borrows from the original string.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    /// * `remap` - the replacement for each character, `None` to keep it
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// let fullwidth = |c: char| {
+    ///     c.to_digit(10)
+    ///         .and_then(|d| char::from_u32(0xFF10 + d))
+    /// };
+    /// assert_eq!("No. 123".unicode_truncate_mapped(8, fullwidth), ("No. １２".into(), 8));
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_truncate_mapped<F>(
+        &self,
+        max_width: usize,
+        remap: F,
+    ) -> (std::borrow::Cow<'_, str>, usize)
+    where
+        F: Fn(char) -> Option<char>;
+
+    /// Pads a string to be `width` in terms of display width. Only available when the `std` feature
+    /// of this library is activated, and it is activated by default.
+    ///
+    /// When `truncate` is true, the string is truncated to `width` if necessary. In case of wide
+    /// characters and truncation point not at character boundary, the longest possible string is
+    /// used, and padded to exact `width` according to `align`.
+    /// See [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) for the behavior of
+    /// truncation.
+    ///
+    /// The padding is computed from the width of the truncated string, so a column left unused by
+    /// a wide character at the truncation point is padded like any other. With center alignment,
+    /// the padding is split evenly between both sides, and an odd column goes to the right.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Panics
+    /// Panics if the length in bytes of the padded string overflows `usize` or exceeds
+    /// `isize::MAX`, which can only happen for a `target_width` close to them. If the length is
+    /// allowed but can not be allocated, the process is aborted like for any failed allocation.
+    /// Use [`try_unicode_pad`](crate::UnicodeTruncateStr::try_unicode_pad) if `target_width` comes
+    /// from untrusted input.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Truncates and pads a string to be `width` in terms of display width with each alignment,
+    /// e.g. for a preview of all of them. Only available when the `std` feature of this library is
+    /// activated, and it is activated by default.
+    ///
+    /// The results are in the order left, center, right, and each is the same as
+    /// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) with that alignment and truncation.
+    /// As the truncation does not depend on the alignment, the string is truncated only once and
+    /// the three results share the truncated slice.
+    ///
+    /// # Arguments
+    /// * `width` - the display width to fit to
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_fit_all_alignments(5), ["你好 ", "你好 ", " 你好"]);
+    /// assert_eq!("ab".unicode_fit_all_alignments(5), ["ab   ", " ab  ", "   ab"]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_fit_all_alignments(&self, width: usize) -> [std::borrow::Cow<'_, str>; 3];
+
+    /// Pads a string like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad), but returns an
+    /// error instead of panicking if the padded string is too long. Only available when the `std`
+    /// feature of this library is activated, and it is activated by default.
+    ///
+    /// The length is checked before anything is allocated, so a huge `target_width`, e.g. from a
+    /// network message, is rejected cheaply. Use
+    /// [`try_unicode_pad_with_limit`](crate::UnicodeTruncateStr::try_unicode_pad_with_limit) to
+    /// reject smaller results as well.
+    ///
+    /// # Errors
+    /// Returns [`PadError`] if the padded string would be longer than `isize::MAX` bytes, the
+    /// maximum length of a `String`.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// assert_eq!("你好".try_unicode_pad(6, Alignment::Right, true).unwrap(), "  你好");
+    /// assert!("你好".try_unicode_pad(usize::MAX, Alignment::Right, true).is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    fn try_unicode_pad(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+    ) -> Result<std::borrow::Cow<'_, str>, PadError> {
+        self.try_unicode_pad_with_limit(target_width, align, truncate, try_pad::MAX_STRING_LEN)
+    }
+
+    /// Pads a string like [`try_unicode_pad`](crate::UnicodeTruncateStr::try_unicode_pad), but
+    /// returns an error if the padded string is longer than `max_len` bytes. Only available when
+    /// the `std` feature of this library is activated, and it is activated by default.
+    ///
+    /// A `max_len` above `isize::MAX` is treated as `isize::MAX`.
+    ///
+    /// # Errors
+    /// Returns [`PadError`] if the padded string would be longer than `max_len` bytes.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    /// * `max_len` - the maximum length in bytes of the padded string
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let err = "你好".try_unicode_pad_with_limit(6, Alignment::Right, true, 4).unwrap_err();
+    /// assert_eq!((err.required(), err.limit()), (8, 4));
+    /// ```
+    #[cfg(feature = "std")]
+    fn try_unicode_pad_with_limit(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        max_len: usize,
+    ) -> Result<std::borrow::Cow<'_, str>, PadError>;
+
+    /// Returns the number of columns of padding
+    /// [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) would add, without building the
+    /// padded string.
+    ///
+    /// This is 0 if the string already is `target_width` wide, or if it is wider and `truncate` is
+    /// false. The padding is split between the sides according to the alignment, but its total
+    /// does not depend on it.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好".unicode_pad_width(6, true), 2);
+    /// assert_eq!("你好吗".unicode_pad_width(5, true), 1);
+    /// assert_eq!("你好吗".unicode_pad_width(5, false), 0);
+    /// ```
+    #[must_use]
+    fn unicode_pad_width(&self, target_width: usize, truncate: bool) -> usize;
+
+    /// Pads a string like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad), appending the
+    /// result to `buf`. Only available when the `std` feature of this library is activated, and it
+    /// is activated by default.
+    ///
+    /// This avoids allocating a new string for each padded cell when building a row in an existing
+    /// buffer. The padding consists of `fill`. When `fill` is wide, the columns it can not cover are
+    /// filled with spaces on the outer side, so the result is never wider than `target_width`.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    /// * `fill` - the character to pad with
+    /// * `buf` - the string to append to
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let mut row = String::new();
+    /// "你好".unicode_pad_into(5, Alignment::Left, true, ' ', &mut row);
+    /// row.push('|');
+    /// "42".unicode_pad_into(4, Alignment::Right, true, '.', &mut row);
+    /// assert_eq!(row, "你好 |..42");
+    /// ```
+    #[cfg(feature = "std")]
+    fn unicode_pad_into(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        fill: char,
+        buf: &mut String,
+    );
+
+    /// Truncates and pads a string to be `width` in terms of display width, writing the result to
+    /// `out`. Only available when the `std` feature of this library is activated, and it is
+    /// activated by default.
+    ///
+    /// This works like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) with truncation
+    /// and `fill` as the padding character, but writes directly to `out` without building a
+    /// string, e.g. when streaming a table to stdout. Padding is written in chunks of several
+    /// characters, so a wide column does not result in a write for every character. A wide `fill`
+    /// is topped off with spaces like in
+    /// [`unicode_pad_into`](crate::UnicodeTruncateStr::unicode_pad_into).
+    ///
+    /// # Errors
+    /// Returns any error returned by `out`.
+    ///
+    /// # Arguments
+    /// * `width` - the display width to fit to
+    /// * `align` - alignment for padding
+    /// * `fill` - the character to pad with
+    /// * `out` - the writer to write the result to
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let mut out = Vec::new();
+    /// "你好吗".unicode_write_fit(5, Alignment::Left, '.', &mut out).unwrap();
+    /// "你好".unicode_write_fit(6, Alignment::Right, ' ', &mut out).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "你好.  你好");
+    /// ```
+    #[cfg(feature = "std")]
+    fn unicode_write_fit<W: std::io::Write>(
+        &self,
+        width: usize,
+        align: Alignment,
+        fill: char,
+        out: &mut W,
+    ) -> std::io::Result<()>;
+
+    /// Pads a string to be `width` in terms of display width, with separate alignments for
+    /// truncation and padding. Only available when the `std` feature of this library is activated,
+    /// and it is activated by default.
+    ///
+    /// This works like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad), except that the
+    /// string is truncated like
+    /// [`unicode_truncate_aligned`](crate::UnicodeTruncateStr::unicode_truncate_aligned) with
+    /// `truncate_align`, while the padding is added according to `pad_align`. For example, a field
+    /// showing the end of a path truncates with right alignment but may still be padded on the
+    /// right.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `truncate_align` - alignment for truncation
+    /// * `pad_align` - alignment for padding
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let path = "/usr/share/你好";
+    /// assert_eq!(path.unicode_pad_asymmetric(8, Alignment::Right, Alignment::Left, true), "are/你好");
+    /// assert_eq!(path.unicode_pad_asymmetric(7, Alignment::Right, Alignment::Left, true), "re/你好");
+    /// assert_eq!("你好".unicode_pad_asymmetric(5, Alignment::Left, Alignment::Right, true), " 你好");
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn unicode_pad_asymmetric(
+        &self,
+        target_width: usize,
+        truncate_align: Alignment,
+        pad_align: Alignment,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str>;
+
+    /// Pads a string like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) into a
+    /// fixed-capacity [`ArrayString`](arrayvec::ArrayString) of `N` bytes. Only available when the
+    /// `arrayvec` feature of this library is activated, and it works without the `std` feature.
+    ///
+    /// This allows padding without an allocator, e.g. on embedded targets.
+    ///
+    /// # Errors
+    /// Returns [`PadArrayError`] if the padded string is longer than `N` bytes.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, UnicodeTruncateStr};
+    ///
+    /// let padded = "你好".unicode_pad_array::<8>(6, Alignment::Right, true).unwrap();
+    /// assert_eq!(padded.as_str(), "  你好");
+    /// assert!("你好吗".unicode_pad_array::<8>(6, Alignment::Right, true).is_err());
+    /// ```
+    #[cfg(feature = "arrayvec")]
+    fn unicode_pad_array<const N: usize>(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+    ) -> Result<arrayvec::ArrayString<N>, PadArrayError>;
+
+    /// Pads a string like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad), after
+    /// converting it to the Unicode normalization form `form`. Only available when the
+    /// `normalization` feature of this library is activated.
+    ///
+    /// Truncation and padding operate on the normalized string, which is also what is returned.
+    /// This keeps results consistent for input mixing precomposed and decomposed characters, e.g.
+    /// both `"caf\u{E9}"` and `"cafe\u{0301}"` are padded to the same string.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to pad to
+    /// * `align` - alignment for truncation and padding
+    /// * `truncate` - whether to truncate string if necessary
+    /// * `form` - the normalization form to convert to
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::{Alignment, NormalizationForm, UnicodeTruncateStr};
+    ///
+    /// let padded = "cafe\u{0301}".unicode_pad_normalized(6, Alignment::Left, true, NormalizationForm::Nfc);
+    /// assert_eq!(padded, "caf\u{E9}  ");
+    /// ```
+    #[cfg(feature = "normalization")]
+    #[must_use]
+    fn unicode_pad_normalized(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        form: NormalizationForm,
+    ) -> std::borrow::Cow<'_, str>;
+}
+
+impl UnicodeTruncateStr for str {
+    #[inline]
+    fn unicode_truncate(&self, max_width: usize) -> (&str, usize) {
+        if fits_by_len(self, max_width) {
+            return (self, graphemes_width(self));
+        }
+        let (result, new_width) = truncate_end_by(self, max_width, UnicodeWidthStr::width);
+        check_width(result, new_width);
+        (result, new_width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_with_caret(
+        &self,
+        max_width: usize,
+        caret: char,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        use std::borrow::Cow;
+
+        let caret_width = char_width(caret);
+        let content_width = match max_width.checked_sub(caret_width) {
+            Some(content_width) => content_width,
+            None => return (Cow::Borrowed(""), 0),
+        };
+
+        let (truncated, columns) = self.unicode_truncate(content_width);
+        // fill the gap left by a wide character only when the string was truncated
+        let gap = if truncated.len() < self.len() {
+            content_width.saturating_sub(columns)
+        } else {
+            0
+        };
+        let mut result = pad(trim_trailing_zwj(truncated), 0, gap, ' ', None);
+        result.push(caret);
+        // unwrap is safe as the result is at most max_width
+        let width = columns
+            .checked_add(gap)
+            .and_then(|w| w.checked_add(caret_width))
+            .unwrap();
+        (Cow::Owned(result), width)
+    }
+
+    #[inline]
+    fn unicode_truncate_units<'a, F, I>(
+        &'a self,
+        max_width: usize,
+        segmenter: F,
+    ) -> (&'a str, usize)
+    where
+        F: FnOnce(&'a str) -> I,
+        I: IntoIterator<Item = (usize, &'a str)>,
+    {
+        let (byte_index, new_width) = truncate_end_index(
+            self.len(),
+            segmenter(self)
+                .into_iter()
+                .map(|(byte_index, unit)| (byte_index, unit.width())),
+            max_width,
+        );
+
+        let result = self
+            .get(..byte_index)
+            .expect("unit byte index should be at a char boundary");
+        (result, new_width)
+    }
+
+    #[inline]
+    fn unicode_truncate_start(&self, max_width: usize) -> (&str, usize) {
+        if fits_by_len(self, max_width) {
+            return (self, graphemes_width(self));
+        }
+        let (result, new_width) = truncate_start_by(self, max_width, UnicodeWidthStr::width);
+        check_width(result, new_width);
+        (result, new_width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_start_with_base(
+        &self,
+        max_width: usize,
+        base: char,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        use std::borrow::Cow;
+
+        let base_width = char_width(base);
+        if let Some(budget) = max_width.checked_sub(base_width) {
+            let (untrimmed, untrimmed_width) =
+                truncate_start_untrimmed(self, budget, UnicodeWidthStr::width);
+            if untrimmed.len() < self.len() && is_orphaned_mark(untrimmed) {
+                let mut result = String::with_capacity(
+                    untrimmed
+                        .len()
+                        .checked_add(base.len_utf8())
+                        .expect("Result should fit in a new String"),
+                );
+                result.push(base);
+                result += untrimmed;
+                // unwrap is safe as the result is at most max_width
+                let width = untrimmed_width.checked_add(base_width).unwrap();
+                return (Cow::Owned(result), width);
+            }
+        }
+        let (result, width) = self.unicode_truncate_start(max_width);
+        (Cow::Borrowed(result), width)
+    }
+
+    #[inline]
+    fn unicode_truncate_centered(&self, max_width: usize) -> (&str, usize) {
+        let (range, result_width) = self.unicode_truncate_centered_range(max_width);
+        // unwrap is safe as the range is at grapheme boundaries
+        let result = self.get(range).unwrap();
+        check_width(result, result_width);
+        (result, result_width)
+    }
+
+    #[inline]
+    fn unicode_truncate_utf16(&self, max_width: usize) -> (&str, usize, usize) {
+        let (result, new_width, utf16_len) = truncate_end_utf16(self, max_width);
+        check_width(result, new_width);
+        (result, new_width, utf16_len)
+    }
+
+    #[inline]
+    fn unicode_truncate_centered_with_center(&self, max_width: usize) -> (&str, usize, &str) {
+        let (result, result_width) = self.unicode_truncate_centered(max_width);
+        // the column of the center, counted from 0, with the left one when there are two
+        let center_column = result_width.saturating_sub(1) / 2;
+        let mut column = 0_usize;
+        let center = result
+            .graphemes(true)
+            .find(|grapheme| {
+                column = column.saturating_add(grapheme.width());
+                column > center_column
+            })
+            .unwrap_or("");
+        (result, result_width, center)
+    }
+
+    #[inline]
+    fn unicode_truncate_with_model(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        model: &dyn WidthModel,
+    ) -> (&str, usize) {
+        let grapheme_width = |grapheme: &str| model.grapheme_width(grapheme);
+        match align {
+            Alignment::Left => truncate_end_by(self, max_width, grapheme_width),
+            Alignment::Center => truncate_centered_by(self, max_width, grapheme_width),
+            Alignment::Right => truncate_start_by(self, max_width, grapheme_width),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_bidi_safe(
+        &self,
+        max_width: usize,
+        align: Alignment,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        let (truncated, width) = self.unicode_truncate_aligned(max_width, align);
+        if truncated.len() == self.len() {
+            return (std::borrow::Cow::Borrowed(truncated), width);
+        }
+        (bidi::balance(truncated), width)
+    }
+
+    #[inline]
+    fn unicode_truncate_last_grapheme(&self, max_width: usize) -> Option<(Range<usize>, &str)> {
+        let (result, _) = self.unicode_truncate(max_width);
+        // the result ends at a grapheme boundary, so its last grapheme is the same as in self
+        result
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(byte_index, grapheme)| (byte_index..result.len(), grapheme))
+    }
+
+    #[cfg(feature = "compact")]
+    #[inline]
+    fn unicode_truncate_compact(&self, max_width: usize) -> (&str, usize) {
+        truncate_end_by(self, max_width, |grapheme: &str| {
+            CompactWidthModel.grapheme_width(grapheme)
+        })
+    }
+
+    #[inline]
+    fn unicode_truncate_trim_trailing(&self, max_width: usize) -> (&str, usize) {
+        let (result, width) = self.unicode_truncate(max_width);
+        let trimmed = result.trim_end();
+        // whitespace never extends a preceding grapheme, so the trimmed part is whole graphemes
+        // unwrap is safe as trim_end returns a prefix
+        let removed = result.get(trimmed.len()..).unwrap();
+        (trimmed, width.saturating_sub(graphemes_width(removed)))
+    }
+
+    #[inline]
+    fn unicode_truncate_strict_width(
+        &self,
+        max_width: usize,
+    ) -> Result<(&str, usize), UnknownWidthChar> {
+        let (result, width) = self.unicode_truncate(max_width);
+        strict::check_known_width(result)?;
+        Ok((result, width))
+    }
+
+    #[inline]
+    fn unicode_truncate_full(&self, max_width: usize) -> (&str, usize, FitOutcome) {
+        let (result, width) = self.unicode_truncate(max_width);
+        let outcome = FitOutcome::of(self.len(), result.len(), width, max_width);
+        (result, width, outcome)
+    }
+
+    #[inline]
+    fn unicode_truncate_start_full(&self, max_width: usize) -> (&str, usize, FitOutcome) {
+        let (result, width) = self.unicode_truncate_start(max_width);
+        let outcome = FitOutcome::of(self.len(), result.len(), width, max_width);
+        (result, width, outcome)
+    }
+
+    #[inline]
+    fn unicode_truncate_centered_full(&self, max_width: usize) -> (&str, usize, FitOutcome) {
+        let (result, width) = self.unicode_truncate_centered(max_width);
+        let outcome = FitOutcome::of(self.len(), result.len(), width, max_width);
+        (result, width, outcome)
+    }
+
+    #[inline]
+    fn unicode_truncate_range(&self, max_width: usize) -> (Range<usize>, usize) {
+        let (result, width) = self.unicode_truncate(max_width);
+        (0..result.len(), width)
+    }
+
+    #[inline]
+    fn unicode_truncate_start_range(&self, max_width: usize) -> (Range<usize>, usize) {
+        let (result, width) = self.unicode_truncate_start(max_width);
+        // unwrap is safe as the result is a suffix of self
+        let start = self.len().checked_sub(result.len()).unwrap();
+        (start..self.len(), width)
+    }
+
+    #[inline]
+    fn unicode_truncate_centered_range(&self, max_width: usize) -> (Range<usize>, usize) {
+        if fits_by_len(self, max_width) {
+            return (0..self.len(), graphemes_width(self));
+        }
+        truncate_centered_range_by(self, max_width, UnicodeWidthStr::width)
+    }
+
+    #[inline]
+    fn unicode_truncate_around(&self, pivot_byte: usize, max_width: usize) -> (&str, usize) {
+        let (result, width) = truncate_around(self, pivot_byte, max_width);
+        check_width(result, width);
+        (result, width)
+    }
+
+    #[inline]
+    fn unicode_truncate_with_zwj_policy(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        policy: ZwjWidth,
+    ) -> (&str, usize) {
+        let grapheme_width = |grapheme: &str| policy.grapheme_width(grapheme);
+        match align {
+            Alignment::Left => truncate_end_by(self, max_width, grapheme_width),
+            Alignment::Center => truncate_centered_by(self, max_width, grapheme_width),
+            Alignment::Right => truncate_start_by(self, max_width, grapheme_width),
+        }
+    }
+
+    #[inline]
+    fn unicode_truncate_smart(
+        &self,
+        max_width: usize,
+        config: SmartTruncateConfig,
+    ) -> (&str, usize) {
+        smart::truncate_smart(self, max_width, config)
+    }
+
+    #[inline]
+    fn unicode_truncate_at(
+        &self,
+        max_width: usize,
+        breakpoints: &[char],
+        fallback: bool,
+    ) -> (&str, usize) {
+        smart::truncate_at_breakpoints(self, max_width, breakpoints, fallback)
+    }
+
+    #[inline]
+    fn unicode_chunks(&self, max_width: usize) -> UnicodeChunks<'_> {
+        UnicodeChunks::new(self, max_width)
+    }
+
+    #[inline]
+    fn unicode_column_chunks(&self, max_width: usize) -> UnicodeColumnChunks<'_> {
+        UnicodeColumnChunks::new(self, max_width)
+    }
+
+    #[inline]
+    fn unicode_wrap_hard(&self, max_width: usize) -> UnicodeWrapHard<'_> {
+        UnicodeWrapHard::new(self, max_width)
+    }
+
+    #[inline]
+    fn unicode_wrap_words(&self, max_width: usize) -> UnicodeWrapWords<'_> {
+        UnicodeWrapWords::new(self, max_width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_flow_columns(&self, column_width: usize, num_columns: usize) -> Vec<(&str, usize)> {
+        wrap::flow_columns(self, column_width, num_columns)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_clamp_lines(
+        &self,
+        max_width: usize,
+        max_lines: usize,
+        ellipsis: &str,
+    ) -> (Vec<std::borrow::Cow<'_, str>>, bool) {
+        wrap::clamp_lines(self, max_width, max_lines, ellipsis)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_fit_box(
+        &self,
+        width: usize,
+        height: usize,
+        align: Alignment,
+        fill_to_height: bool,
+    ) -> BoxFit {
+        wrap::fit_box(self, width, height, align, fill_to_height)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_wrap_indented<'a>(
+        &'a self,
+        first_width: usize,
+        continuation_prefix: &'a str,
+        total_width: usize,
+    ) -> UnicodeWrapIndented<'a> {
+        UnicodeWrapIndented::new(self, first_width, continuation_prefix, total_width)
+    }
+
+    #[cfg(feature = "debug")]
+    #[inline]
+    fn unicode_width_ruler(&self) -> String {
+        ruler::ruler(self)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_mapped<F>(
+        &self,
+        max_width: usize,
+        remap: F,
+    ) -> (std::borrow::Cow<'_, str>, usize)
+    where
+        F: Fn(char) -> Option<char>,
+    {
+        use std::borrow::Cow;
+
+        let mut remapped: Option<String> = None;
+        let mut width = 0_usize;
+        let mut end = 0_usize;
+        for (byte_index, grapheme) in self.grapheme_indices(true) {
+            let grapheme_width = match remapped.as_mut() {
+                Some(remapped) => {
+                    let start = remapped.len();
+                    remapped.extend(grapheme.chars().map(|ch| remap(ch).unwrap_or(ch)));
+                    let grapheme_width = remapped.get(start..).map_or(0, UnicodeWidthStr::width);
+                    if width.saturating_add(grapheme_width) > max_width {
+                        remapped.truncate(start);
+                        break;
+                    }
+                    grapheme_width
+                }
+                None => {
+                    let mut chars = grapheme.char_indices();
+                    let first = chars
+                        .by_ref()
+                        .find_map(|(char_index, ch)| remap(ch).map(|ch| (char_index, ch)));
+                    let (char_index, ch) = match first {
+                        Some(first) => first,
+                        None => {
+                            let grapheme_width = grapheme.width();
+                            if width.saturating_add(grapheme_width) > max_width {
+                                break;
+                            }
+                            width = width.saturating_add(grapheme_width);
+                            end = byte_index.saturating_add(grapheme.len());
+                            continue;
+                        }
+                    };
+                    // the string is only copied once a remapped grapheme is kept
+                    let mut mapped_grapheme = String::with_capacity(grapheme.len());
+                    // unwrap is safe as char_index comes from char_indices
+                    mapped_grapheme.push_str(grapheme.get(..char_index).unwrap());
+                    mapped_grapheme.push(ch);
+                    mapped_grapheme.extend(chars.map(|(_, ch)| remap(ch).unwrap_or(ch)));
+                    let grapheme_width = mapped_grapheme.width();
+                    if width.saturating_add(grapheme_width) > max_width {
+                        break;
+                    }
+                    let mut copy = String::with_capacity(self.len());
+                    // unwrap is safe as byte_index comes from grapheme_indices
+                    copy.push_str(self.get(..byte_index).unwrap());
+                    copy.push_str(&mapped_grapheme);
+                    remapped = Some(copy);
+                    grapheme_width
+                }
+            };
+            width = width.saturating_add(grapheme_width);
+            end = byte_index.saturating_add(grapheme.len());
+        }
+
+        match remapped {
+            Some(remapped) => (Cow::Owned(remapped), width),
+            // unwrap is safe as end is the end of a grapheme
+            None => (Cow::Borrowed(self.get(..end).unwrap()), width),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_asymmetric(
+        &self,
+        target_width: usize,
+        truncate_align: Alignment,
+        pad_align: Alignment,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate_aligned(target_width, truncate_align);
+        if !truncate && truncated.len() < self.len() {
+            return Cow::Borrowed(self);
+        }
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, pad_align);
+        Cow::Owned(pad(truncated, left_pad, right_pad, ' ', None))
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[inline]
+    fn unicode_pad_array<const N: usize>(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+    ) -> Result<arrayvec::ArrayString<N>, PadArrayError> {
+        array::pad_array(self, target_width, align, truncate)
+    }
+
+    #[cfg(feature = "normalization")]
+    #[inline]
+    fn unicode_pad_normalized(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        form: NormalizationForm,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        match form.normalize(self) {
+            Cow::Borrowed(normalized) => normalized.unicode_pad(target_width, align, truncate),
+            Cow::Owned(normalized) => Cow::Owned(
+                normalized
+                    .unicode_pad(target_width, align, truncate)
+                    .into_owned(),
+            ),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        // truncation stops scanning once target_width is exceeded, so it doubles as the check
+        // whether the string reaches target_width without measuring all of it. Without
+        // truncation it is still the only scan, and its width is measured grapheme by grapheme,
+        // which `str::width` does not match e.g. for a ZWJ between graphemes
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if !truncate && truncated.len() < self.len() {
+            return Cow::Borrowed(self);
+        }
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        // the string is less than width, or truncated to less than width
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        Cow::Owned(pad(truncated, left_pad, right_pad, ' ', None))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_fit_all_alignments(&self, width: usize) -> [std::borrow::Cow<'_, str>; 3] {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate(width);
+        if columns == width {
+            return [
+                Cow::Borrowed(truncated),
+                Cow::Borrowed(truncated),
+                Cow::Borrowed(truncated),
+            ];
+        }
+
+        let diff = width.saturating_sub(columns);
+        [Alignment::Left, Alignment::Center, Alignment::Right].map(|align| {
+            let (left_pad, right_pad) = split_padding(diff, align);
+            Cow::Owned(pad(truncated, left_pad, right_pad, ' ', None))
+        })
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn try_unicode_pad_with_limit(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        max_len: usize,
+    ) -> Result<std::borrow::Cow<'_, str>, PadError> {
+        try_pad::try_pad(self, target_width, align, truncate, max_len)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_into(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        fill: char,
+        buf: &mut String,
+    ) {
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if !truncate && truncated.len() < self.len() {
+            *buf += self;
+            return;
+        }
+
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        pad_into(buf, truncated, left_pad, right_pad, fill, None);
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_write_fit<W: std::io::Write>(
+        &self,
+        width: usize,
+        align: Alignment,
+        fill: char,
+        out: &mut W,
+    ) -> std::io::Result<()> {
+        let (truncated, columns) = self.unicode_truncate(width);
+        let (left_pad, right_pad) = split_padding(width.saturating_sub(columns), align);
+        let (left_fills, left_spaces) = split_fill(left_pad, fill);
+        let (right_fills, right_spaces) = split_fill(right_pad, fill);
+        write_fill(out, ' ', left_spaces)?;
+        write_fill(out, fill, left_fills)?;
+        out.write_all(truncated.as_bytes())?;
+        write_fill(out, fill, right_fills)?;
+        write_fill(out, ' ', right_spaces)
+    }
+
+    #[inline]
+    fn unicode_pad_width(&self, target_width: usize, truncate: bool) -> usize {
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if !truncate && truncated.len() < self.len() {
+            return 0;
+        }
+        target_width.saturating_sub(columns)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_ellipsis(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        ellipsis: &str,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate(max_width);
+        if truncated.len() == self.len() {
+            return (Cow::Borrowed(self), columns);
+        }
+
+        let ellipsis_width = ellipsis.width();
+        let budget = match max_width.checked_sub(ellipsis_width) {
+            Some(budget) => budget,
+            None => {
+                let (ellipsis, width) = ellipsis.unicode_truncate(max_width);
+                return (Cow::Owned(ellipsis.to_owned()), width);
+            }
+        };
+
+        let (head, tail) = ellipsis_parts(self, budget, align);
+        let (result, width) = join_ellipsis(head, (ellipsis, ellipsis_width), tail);
+        // the sum is at most max_width, as each part fits in what the others leave
+        debug_assert!(width <= max_width);
+        (Cow::Owned(result), width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_ellipsis_min_content(
+        &self,
+        max_width: usize,
+        align: Alignment,
+        ellipsis: &str,
+        min_content_graphemes: usize,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        use std::borrow::Cow;
+
+        if min_content_graphemes == 0 {
+            return self.unicode_truncate_ellipsis(max_width, align, ellipsis);
+        }
+        let (truncated, columns) = self.unicode_truncate(max_width);
+        if truncated.len() == self.len() {
+            return (Cow::Borrowed(self), columns);
+        }
+
+        let ellipsis_width = ellipsis.width();
+        if let Some(budget) = max_width.checked_sub(ellipsis_width) {
+            let (head, tail) = ellipsis_parts(self, budget, align);
+            let kept = head.0.graphemes(true).chain(tail.0.graphemes(true)).count();
+            if kept >= min_content_graphemes {
+                let (result, width) = join_ellipsis(head, (ellipsis, ellipsis_width), tail);
+                return (Cow::Owned(result), width);
+            }
+        }
+
+        let (head_count, tail_count) = match align {
+            Alignment::Left => (min_content_graphemes, 0),
+            Alignment::Right => (0, min_content_graphemes),
+            Alignment::Center => (
+                min_content_graphemes / 2,
+                min_content_graphemes.saturating_sub(min_content_graphemes / 2),
+            ),
+        };
+        let head_len: usize = self.graphemes(true).take(head_count).map(str::len).sum();
+        let tail_len: usize = self
+            .graphemes(true)
+            .rev()
+            .take(tail_count)
+            .map(str::len)
+            .sum();
+        if head_len.saturating_add(tail_len) >= self.len() {
+            return (Cow::Borrowed(self), graphemes_width(self));
+        }
+        let head = &self[..head_len];
+        // unwrap is safe as the tail is shorter than the string
+        let tail = &self[self.len().checked_sub(tail_len).unwrap()..];
+        let (result, width) = join_ellipsis(
+            (head, graphemes_width(head)),
+            (ellipsis, ellipsis_width),
+            (tail, graphemes_width(tail)),
+        );
+        (Cow::Owned(result), width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_truncate_after_prefix(
+        &self,
+        prefix_width: usize,
+        max_width: usize,
+        ellipsis: &str,
+    ) -> (std::borrow::Cow<'_, str>, usize) {
+        use std::borrow::Cow;
+
+        if prefix_width >= max_width {
+            return self.unicode_truncate_ellipsis(max_width, Alignment::Left, ellipsis);
+        }
+        let (prefix, prefix_columns) = self.unicode_truncate(prefix_width);
+        let value = &self[prefix.len()..];
+        // unwrap is safe as the prefix is at most prefix_width, which is less than max_width
+        let value_width = max_width.checked_sub(prefix_columns).unwrap();
+        let (value, value_columns) =
+            value.unicode_truncate_ellipsis(value_width, Alignment::Left, ellipsis);
+        if prefix.len().saturating_add(value.len()) == self.len() {
+            // nothing was removed, so the value borrows the rest of the string
+            return (
+                Cow::Borrowed(self),
+                prefix_columns.saturating_add(value_columns),
+            );
+        }
+        let mut result = String::with_capacity(prefix.len().saturating_add(value.len()));
+        result.push_str(prefix);
+        result.push_str(&value);
+        // unwrap is safe as the value is at most value_width
+        let width = prefix_columns.checked_add(value_columns).unwrap();
+        (Cow::Owned(result), width)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_fit(
+        &self,
+        width: usize,
+        align: Alignment,
+        ellipsis: &str,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate_ellipsis(width, align, ellipsis);
+        if columns == width {
+            return truncated;
+        }
+
+        let diff = width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        Cow::Owned(pad(&truncated, left_pad, right_pad, ' ', None))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_sep(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        fill: char,
+        sep: char,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        Cow::Owned(pad(truncated, left_pad, right_pad, fill, Some(sep)))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_opt_fill(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        fill: Option<char>,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate_aligned(target_width, align);
+        match fill {
+            Some(fill) if columns < target_width => {
+                let diff = target_width.saturating_sub(columns);
+                let (left_pad, right_pad) = split_padding(diff, align);
+                Cow::Owned(pad(truncated, left_pad, right_pad, fill, None))
+            }
+            _ => Cow::Borrowed(truncated),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_strategy(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        fill: &dyn FillStrategy,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if !truncate && truncated.len() < self.len() {
+            return Cow::Borrowed(self);
+        }
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        Cow::Owned(fill::pad_with(
+            truncated, columns, left_pad, right_pad, fill,
+        ))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_right_gutter(
+        &self,
+        target_width: usize,
+        right_gutter: usize,
+        truncate: bool,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        let content_width = target_width.saturating_sub(right_gutter);
+        let (truncated, columns) = self.unicode_truncate(content_width);
+        if !truncate && truncated.len() < self.len() {
+            return Cow::Borrowed(self);
+        }
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        let left_pad = content_width.saturating_sub(columns);
+        // the gutter is what is left of target_width, so a wide gutter does not overshoot it
+        let gutter = target_width.saturating_sub(content_width);
+        Cow::Owned(pad(truncated, left_pad, gutter, ' ', None))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn unicode_pad_asymmetric_fill(
+        &self,
+        target_width: usize,
+        align: Alignment,
+        truncate: bool,
+        left_fill: char,
+        right_fill: char,
+    ) -> std::borrow::Cow<'_, str> {
+        use std::borrow::Cow;
+
+        assert_eq!(
+            char_width(left_fill),
+            1,
+            "left_fill should be 1 column wide"
+        );
+        assert_eq!(
+            char_width(right_fill),
+            1,
+            "right_fill should be 1 column wide"
+        );
+
+        let (truncated, columns) = self.unicode_truncate(target_width);
+        if !truncate && truncated.len() < self.len() {
+            return Cow::Borrowed(self);
+        }
+        if columns == target_width {
+            return Cow::Borrowed(truncated);
+        }
+
+        let diff = target_width.saturating_sub(columns);
+        let (left_pad, right_pad) = split_padding(diff, align);
+        let mut result = pad(truncated, left_pad, 0, left_fill, None);
+        pad_into(&mut result, "", 0, right_pad, right_fill, None);
+        Cow::Owned(result)
+    }
+}
+
+/// Returns the display width of a single character, measured in the same way as strings.
+#[cfg(feature = "std")]
+#[inline]
+fn char_width(c: char) -> usize {
+    c.encode_utf8(&mut [0; 4]).width()
+}
+
+/// Splits `diff` columns of padding into left and right padding according to `align`.
+#[cfg(any(feature = "std", feature = "arrayvec"))]
+#[inline]
+fn split_padding(diff: usize, align: Alignment) -> (usize, usize) {
+    let (left_pad, right_pad) = match align {
+        Alignment::Left => (0, diff),
+        Alignment::Right => (diff, 0),
+        Alignment::Center => (diff / 2, diff.saturating_sub(diff / 2)),
+    };
+    debug_assert_eq!(diff, left_pad.saturating_add(right_pad));
+    (left_pad, right_pad)
+}
+
+/// Surrounds `content` with `left_pad` and `right_pad` columns of `fill`. When `sep` is given, it
+/// takes the columns of padding next to the content on each padded side it fits in.
+#[cfg(feature = "std")]
+#[inline]
+fn pad(content: &str, left_pad: usize, right_pad: usize, fill: char, sep: Option<char>) -> String {
+    let mut result = String::new();
+    pad_into(&mut result, content, left_pad, right_pad, fill, sep);
+    result
+}
+
+/// Appends `content` surrounded by padding to `buf`, like [`pad`].
+#[cfg(feature = "std")]
+#[inline]
+fn pad_into(
+    buf: &mut String,
+    content: &str,
+    left_pad: usize,
+    right_pad: usize,
+    fill: char,
+    sep: Option<char>,
+) {
+    // every column takes at most one character, and a space is never longer than another one
+    let fill_len = left_pad
+        .saturating_add(right_pad)
+        .checked_mul(fill.len_utf8().max(sep.map_or(0, char::len_utf8)))
+        .expect("Padded result should fit in a new String");
+    let new_len = content
+        .len()
+        .checked_add(fill_len)
+        .expect("Padded result should fit in a new String");
+    let (left_sep, left_fill) = split_sep(left_pad, sep);
+    let (right_sep, right_fill) = split_sep(right_pad, sep);
+    let (left_fills, left_spaces) = split_fill(left_fill, fill);
+    let (right_fills, right_spaces) = split_fill(right_fill, fill);
+
+    buf.reserve(new_len);
+    buf.extend(core::iter::repeat(' ').take(left_spaces));
+    buf.extend(core::iter::repeat(fill).take(left_fills));
+    buf.extend(left_sep);
+    *buf += content;
+    buf.extend(right_sep);
+    buf.extend(core::iter::repeat(fill).take(right_fills));
+    buf.extend(core::iter::repeat(' ').take(right_spaces));
+}
+
+/// Returns the parts of `s` to keep before and after an ellipsis, so that they fit in `budget`
+/// columns together, as `(text, width)` pairs.
+#[cfg(feature = "std")]
+#[inline]
+fn ellipsis_parts(s: &str, budget: usize, align: Alignment) -> ((&str, usize), (&str, usize)) {
+    match align {
+        Alignment::Left => (s.unicode_truncate(budget), ("", 0)),
+        Alignment::Right => (("", 0), s.unicode_truncate_start(budget)),
+        Alignment::Center => {
+            // give the extra column of an odd budget and any slack to the tail
+            let head = s.unicode_truncate(budget.saturating_sub(budget / 2));
+            let tail = s.unicode_truncate_start(budget.saturating_sub(head.1));
+            (head, tail)
+        }
+    }
+}
+
+/// Joins `head`, `ellipsis` and `tail`, given as `(text, width)` pairs, and returns the result with
+/// its width.
+#[cfg(feature = "std")]
+#[inline]
+fn join_ellipsis(
+    head: (&str, usize),
+    ellipsis: (&str, usize),
+    tail: (&str, usize),
+) -> (String, usize) {
+    let mut result = String::with_capacity(
+        head.0
+            .len()
+            .saturating_add(ellipsis.0.len())
+            .saturating_add(tail.0.len()),
+    );
+    result.push_str(trim_trailing_zwj(head.0));
+    result.push_str(ellipsis.0);
+    result.push_str(tail.0);
+    let width = head.1.saturating_add(ellipsis.1).saturating_add(tail.1);
+    (result, width)
+}
+
+/// Removes ZWJs from the end of `s`, so that text appended to it is not joined with its last
+/// grapheme, e.g. into an unexpected emoji ZWJ sequence. ZWJs are zero-width, so this does not
+/// change the display width.
+#[cfg(feature = "std")]
+#[inline]
+fn trim_trailing_zwj(s: &str) -> &str {
+    s.trim_end_matches(zwj::ZWJ)
+}
+
+/// Returns the separator to put in `cols` columns of padding, if it fits, and the columns left for
+/// the fill.
+#[cfg(feature = "std")]
+#[inline]
+fn split_sep(cols: usize, sep: Option<char>) -> (Option<char>, usize) {
+    match sep {
+        Some(sep) if cols > 0 => {
+            // a zero-width separator still takes a column, like it always has
+            let sep_width = char_width(sep).max(1);
+            match cols.checked_sub(sep_width) {
+                Some(rest) => (Some(sep), rest),
+                None => (None, cols),
+            }
+        }
+        _ => (None, cols),
+    }
+}
+
+/// Splits `cols` columns of padding into the number of copies of `fill` and of spaces to fill them
+/// with.
+///
+/// A wide `fill` is repeated as often as it fits, and the columns it can not cover are topped off
+/// with spaces, so that the padding is never wider than `cols`.
+#[cfg(feature = "std")]
+#[inline]
+fn split_fill(cols: usize, fill: char) -> (usize, usize) {
+    // a zero-width fill still takes a column, like it always has
+    let fill_width = char_width(fill).max(1);
+    // unwrap is safe as fill_width is at least 1
+    (
+        cols.checked_div(fill_width).unwrap(),
+        cols.checked_rem(fill_width).unwrap(),
+    )
+}
+
+/// Capacity in bytes of the buffer `fill` is repeated in by [`write_fill`].
+#[cfg(feature = "std")]
+const FILL_CHUNK_CAPACITY: usize = 64;
+
+/// Writes `count` copies of `fill` to `out`, a chunk of them at a time.
+#[cfg(feature = "std")]
+fn write_fill<W: std::io::Write>(out: &mut W, fill: char, count: usize) -> std::io::Result<()> {
+    let mut chunk = [0; FILL_CHUNK_CAPACITY];
+    let fill_len = fill.len_utf8();
+    let mut chunk_count = 0usize;
+    for slot in chunk.chunks_exact_mut(fill_len) {
+        fill.encode_utf8(slot);
+        // unwrap is safe as the count is at most the capacity
+        chunk_count = chunk_count.checked_add(1).unwrap();
+    }
+
+    let mut remaining = count;
+    while remaining > 0 {
+        let n = remaining.min(chunk_count);
+        // unwrap is safe as the chunk holds chunk_count characters
+        out.write_all(&chunk[..n.checked_mul(fill_len).unwrap()])?;
+        // unwrap is safe as n is at most remaining
+        remaining = remaining.checked_sub(n).unwrap();
+    }
+    Ok(())
+}
+
+/// Returns `true` if `s` is known to fit in `max_width` from its length alone.
+///
+/// This relies on the display width of a string never exceeding its length in bytes: ASCII
+/// characters are at most 1 column wide, and wide characters take at least 3 bytes in UTF-8. The
+/// `width_bounded_by_len` property test guards the assumption against changes in
+/// [`unicode_width`].
+#[inline]
+fn fits_by_len(s: &str, max_width: usize) -> bool {
+    s.len() <= max_width
+}
+
+/// Checks in builds with debug assertions that `result` is `width` wide, as measured by
+/// [`graphemes_width`].
+///
+/// A mismatch panics, unless it is passed to a hook registered with the `mismatch-hook` feature.
+#[inline]
+fn check_width(result: &str, width: usize) {
+    if cfg!(debug_assertions) {
+        let actual_width = graphemes_width(result);
+        if actual_width != width && !report_width_mismatch(result, width, actual_width) {
+            panic!(
+                "truncated string {:?} is {} columns wide but reported as {}",
+                result, actual_width, width
+            );
+        }
+    }
+}
+
+#[cfg(feature = "mismatch-hook")]
+#[inline]
+fn report_width_mismatch(result: &str, reported_width: usize, actual_width: usize) -> bool {
+    hook::report(result, reported_width, actual_width)
+}
+
+#[cfg(not(feature = "mismatch-hook"))]
+#[inline]
+fn report_width_mismatch(_result: &str, _reported_width: usize, _actual_width: usize) -> bool {
+    false
+}
+
+/// Returns the display width of `s` as the sum of the widths of its graphemes.
+///
+/// This is the width the truncation methods accumulate, which can differ from
+/// [`UnicodeWidthStr::width`] of the whole string, e.g. when a ZWJ joins two emoji that are not
+/// in the same grapheme. Measuring results the same way keeps the returned width consistent with
+/// the returned slice.
+#[inline]
+fn graphemes_width(s: &str) -> usize {
+    s.graphemes(true)
+        .map(UnicodeWidthStr::width)
+        .fold(0, usize::saturating_add)
+}
+
+/// Truncates by removing the end graphemes, measuring each grapheme with `grapheme_width`.
+#[inline]
+fn truncate_end_by(
+    s: &str,
+    max_width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (&str, usize) {
+    let (byte_index, new_width) = truncate_end_index(
+        s.len(),
+        s.grapheme_indices(true)
+            // map to byte index and the width of grapheme at the index
+            .map(|(byte_index, grapheme)| (byte_index, grapheme_width(grapheme))),
+        max_width,
+    );
+
+    // unwrap is safe as the index comes from grapheme_indices
+    (s.get(..byte_index).unwrap(), new_width)
+}
+
+/// Truncates by removing the end graphemes like [`truncate_end_by`] with the default width, and
+/// counts the UTF-16 code units of the result along the way.
+#[inline]
+fn truncate_end_utf16(s: &str, max_width: usize) -> (&str, usize, usize) {
+    let mut byte_index = 0_usize;
+    let mut new_width = 0_usize;
+    let mut utf16_len = 0_usize;
+    for grapheme in s.graphemes(true) {
+        // saturated like in truncate_end_index
+        let width = new_width.saturating_add(grapheme.width());
+        if width > max_width {
+            break;
+        }
+        new_width = width;
+        // the lengths are at most the length of the string in bytes
+        byte_index = byte_index.saturating_add(grapheme.len());
+        utf16_len = utf16_len.saturating_add(grapheme.encode_utf16().count());
+    }
+
+    // unwrap is safe as the index is at a grapheme boundary
+    (s.get(..byte_index).unwrap(), new_width, utf16_len)
+}
+
+/// Truncates by removing the start graphemes, measuring each grapheme with `grapheme_width`.
+///
+/// Zero-width graphemes and combining marks left at the start are removed as well, see
+/// [`trim_detached_start`].
+#[inline]
+fn truncate_start_by(
+    s: &str,
+    max_width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (&str, usize) {
+    let (result, new_width) = truncate_start_untrimmed(s, max_width, &grapheme_width);
+    if result.len() == s.len() {
+        return (result, new_width);
+    }
+    trim_detached_start(result, new_width, grapheme_width)
+}
+
+/// Truncates by removing the start graphemes, keeping zero-width graphemes and combining marks
+/// left at the start.
+#[inline]
+fn truncate_start_untrimmed(
+    s: &str,
+    max_width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (&str, usize) {
+    let (byte_index, new_width) = truncate_start_index(
+        s.len(),
+        s.grapheme_indices(true)
+            // instead of start checking from the start do so from the end
+            .rev()
+            // map to byte index and the width of grapheme start at the index
+            .map(|(byte_index, grapheme)| (byte_index, grapheme_width(grapheme))),
+        max_width,
+    );
+
+    // unwrap is safe as the index comes from grapheme_indices
+    (s.get(byte_index..).unwrap(), new_width)
+}
+
+/// Finds the byte index to truncate at and the width from it to the end, given the byte index and
+/// width of each segment from the end in a string of `len` bytes.
+///
+/// The widths are summed with saturation, like in [`truncate_end_index`].
+#[inline]
+fn truncate_start_index(
+    len: usize,
+    segments_rev: impl Iterator<Item = (usize, usize)>,
+    max_width: usize,
+) -> (usize, usize) {
+    segments_rev
+        // fold to byte index and the width from end to the index
+        .scan(0, |sum: &mut usize, (byte_index, segment_width)| {
+            *sum = sum.saturating_add(segment_width);
+            Some((byte_index, *sum))
+        })
+        .take_while(|&(_, current_width)| current_width <= max_width)
+        .last()
+        .unwrap_or((len, 0))
+}
+
+/// Removes the graphemes at the start of `s` that are zero-width or consist of combining marks
+/// only, and subtracts their width from `width`.
+///
+/// Such graphemes are left when truncation removes the character they were following, and would
+/// attach to whatever is rendered before the result, or render on their own, often with a dotted
+/// circle.
+#[inline]
+fn trim_detached_start(
+    s: &str,
+    width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (&str, usize) {
+    let mut width = width;
+    for (byte_index, grapheme) in s.grapheme_indices(true) {
+        let segment_width = grapheme_width(grapheme);
+        if !is_detached(grapheme, segment_width == 0) {
+            // unwrap is safe as the index comes from grapheme_indices
+            return (s.get(byte_index..).unwrap(), width);
+        }
+        width = width.saturating_sub(segment_width);
+    }
+    ("", 0)
+}
+
+/// Returns `true` if the grapheme has no visible base of its own, so it must not start a truncated
+/// result.
+#[inline]
+fn is_detached(grapheme: &str, zero_width: bool) -> bool {
+    zero_width || is_orphaned_mark(grapheme)
+}
+
+/// Returns `true` if the grapheme starts with a character that would extend a preceding base
+/// character, like a combining mark, meaning it has no base of its own.
+#[inline]
+fn is_orphaned_mark(grapheme: &str) -> bool {
+    let first = match grapheme.chars().next() {
+        Some(first) => first,
+        None => return false,
+    };
+    let mut buf = [b'a'; 5];
+    // unwrap is safe as the buffer has room for the base and any char
+    let len = first
+        .encode_utf8(&mut buf[1..])
+        .len()
+        .checked_add(1)
+        .unwrap();
+    // unwrap is safe as the buffer holds whole chars
+    let pair = core::str::from_utf8(&buf[..len]).unwrap();
+    pair.graphemes(true).nth(1).is_none()
+}
+
+/// Truncates to a window around the grapheme containing `pivot_byte`.
+fn truncate_around(s: &str, pivot_byte: usize, max_width: usize) -> (&str, usize) {
+    let pivot = s
+        .grapheme_indices(true)
+        .take_while(|&(byte_index, _)| byte_index <= pivot_byte)
+        .last();
+    let (pivot_start, pivot_grapheme) = match pivot {
+        Some(pivot) => pivot,
+        None => return ("", 0),
+    };
+    let mut width = pivot_grapheme.width();
+    if width > max_width {
+        return ("", 0);
+    }
+
+    // unwrap is safe as the grapheme is part of s
+    let mut start = pivot_start;
+    let mut end = pivot_start.checked_add(pivot_grapheme.len()).unwrap();
+    // measure each grapheme once, as it may be peeked several times
+    let with_width = |grapheme: &str| (grapheme.len(), grapheme.width());
+    let mut before = s[..start].graphemes(true).rev().map(with_width).peekable();
+    let mut after = s[end..].graphemes(true).map(with_width).peekable();
+    let (mut before_width, mut after_width) = (0usize, 0usize);
+    loop {
+        // unwrap is safe as width <= max_width
+        let budget = max_width.checked_sub(width).unwrap();
+        let fits = |&(_, grapheme_width): &(usize, usize)| grapheme_width <= budget;
+        let before_fits = before.peek().map_or(false, fits);
+        let after_fits = after.peek().map_or(false, fits);
+        let grow_before = match (before_fits, after_fits) {
+            (false, false) => break,
+            (true, true) => before_width < after_width,
+            (before_fits, _) => before_fits,
+        };
+        // unwraps are safe as the graphemes were peeked, and widths are at most max_width
+        if grow_before {
+            let (grapheme_len, grapheme_width) = before.next().unwrap();
+            start = start.checked_sub(grapheme_len).unwrap();
+            before_width = before_width.checked_add(grapheme_width).unwrap();
+            width = width.checked_add(grapheme_width).unwrap();
+        } else {
+            let (grapheme_len, grapheme_width) = after.next().unwrap();
+            end = end.checked_add(grapheme_len).unwrap();
+            after_width = after_width.checked_add(grapheme_width).unwrap();
+            width = width.checked_add(grapheme_width).unwrap();
+        }
+    }
+
+    // unwrap is safe as the indices are at grapheme boundaries
+    (s.get(start..end).unwrap(), width)
+}
+
+/// Truncates by removing graphemes at both start and end, measuring each grapheme with
+/// `grapheme_width`.
+#[inline]
+fn truncate_centered_by(
+    s: &str,
+    max_width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (&str, usize) {
+    let (range, width) = truncate_centered_range_by(s, max_width, grapheme_width);
+    // unwrap is safe as the range is at grapheme boundaries
+    (s.get(range).unwrap(), width)
+}
+
+/// Finds the byte range kept by [`truncate_centered_by`] and its width.
+///
+/// The result is the widest slice that fits, i.e. the one removing the least width in total. Among
+/// those, the removal is balanced between start and end as well as possible, removing more from the
+/// end on ties.
+///
+/// The widths are summed as `u128`, so the result is exact even if the total width of the string
+/// does not fit in `usize`.
+#[inline]
+fn truncate_centered_range_by(
+    s: &str,
+    max_width: usize,
+    grapheme_width: impl Fn(&str) -> usize,
+) -> (Range<usize>, usize) {
+    // Widths are widened before being summed. The sums can not overflow as there are fewer
+    // graphemes than bytes, each at most usize::MAX wide.
+    let grapheme_width = |grapheme: &str| grapheme_width(grapheme) as u128;
+    let original_width = s
+        .graphemes(true)
+        .map(&grapheme_width)
+        .fold(0u128, |sum, width| sum.checked_add(width).unwrap());
+    if original_width <= max_width as u128 {
+        // unwrap is safe as the width is at most max_width
+        return (0..s.len(), usize::try_from(original_width).unwrap());
+    }
+    if max_width == 0 {
+        return (0..0, 0);
+    }
+
+    // We need to remove at least this much
+    // unwrap is safe as original_width > max_width
+    let min_removal_width = original_width.checked_sub(max_width as u128).unwrap();
+
+    // The shortest prefix removing at least min_removal_width, along with the zero-width graphemes
+    // after it. Removing more from the start is never needed, so the work is proportional to the
+    // removal rather than the length of the string.
+    let mut prefix_width = 0u128;
+    let mut prefix_end = s.len();
+    for (byte_index, grapheme) in s.grapheme_indices(true) {
+        let width = grapheme_width(grapheme);
+        if prefix_width >= min_removal_width && !is_detached(grapheme, width == 0) {
+            prefix_end = byte_index;
+            break;
+        }
+        // unwrap is safe as the sum is at most original_width
+        prefix_width = prefix_width.checked_add(width).unwrap();
+    }
+
+    // Start positions with decreasing removed width, paired with end positions with increasing
+    // removed width. For each width, only the position right before a grapheme with width is
+    // used, so zero-width graphemes are removed at the start and kept at the end. Starting at
+    // combining marks without a base is skipped as well, see is_detached.
+    // unwrap is safe as prefix_end is at a grapheme boundary
+    let starts = core::iter::once((prefix_end, prefix_width)).chain(
+        s.get(..prefix_end)
+            .unwrap()
+            .grapheme_indices(true)
+            .rev()
+            .scan(prefix_width, |removed, (byte_index, grapheme)| {
+                let width = grapheme_width(grapheme);
+                // unwrap is safe as the graphemes are part of the prefix
+                *removed = removed.checked_sub(width).unwrap();
+                Some((byte_index, *removed, is_detached(grapheme, width == 0)))
+            })
+            .filter(|&(_, _, detached)| !detached)
+            .map(|(byte_index, removed, _)| (byte_index, removed)),
+    );
+    let mut ends = s
+        .grapheme_indices(true)
+        .rev()
+        .scan(0u128, |removed, (byte_index, grapheme)| {
+            let width = grapheme_width(grapheme);
+            // unwrap is safe as the sum is at most original_width
+            *removed = removed.checked_add(width).unwrap();
+            Some((byte_index, *removed, width))
+        })
+        .filter(|&(_, _, width)| width > 0)
+        .map(|(byte_index, removed, _)| (byte_index, removed));
+
+    let mut end = (s.len(), 0u128);
+    let mut best: Option<(usize, usize, u128)> = None;
+    let mut best_key = (u128::MAX, u128::MAX, true);
+    for (start_index, start_removed) in starts {
+        let needed = min_removal_width.saturating_sub(start_removed);
+        while end.1 < needed {
+            match ends.next() {
+                Some(next) => end = next,
+                None => break,
+            }
+        }
+        let (end_index, end_removed) = end;
+        if end_removed < needed || start_index > end_index {
+            continue;
+        }
+        // unwrap is safe as the removals do not overlap
+        let removed = start_removed.checked_add(end_removed).unwrap();
+        let imbalance = start_removed
+            .max(end_removed)
+            .saturating_sub(start_removed.min(end_removed));
+        let key = (removed, imbalance, start_removed > end_removed);
+        if key < best_key {
+            best_key = key;
+            best = Some((start_index, end_index, removed));
+        }
+    }
+    // should not happen as removing the whole string is always possible, but a sane default is
+    // to remove everything
+    let (start_index, end_index, removed_width) = best.unwrap_or((0, 0, original_width));
+
+    // unwraps are safe as removed is at least min_removal_width, so the rest fits in max_width
+    let result_width = usize::try_from(original_width.checked_sub(removed_width).unwrap()).unwrap();
+    (start_index..end_index, result_width)
+}
+
+/// Computes `total_columns * fraction` rounded down and clamped to `0..=total_columns`.
+#[inline]
+fn fraction_of_columns(total_columns: usize, fraction: f32) -> usize {
+    // float to int casts round toward zero, which is down for positive numbers, and saturate with
+    // NaN mapped to 0
+    ((total_columns as f32 * fraction) as usize).min(total_columns)
+}
+
+/// Finds the byte index to truncate at and the width up to it, given the byte index and width of
+/// each segment in a string of `len` bytes.
+///
+/// The widths are summed with saturation, so once the sum reaches `usize::MAX` it is treated as
+/// wider than any `max_width` but `usize::MAX` itself.
+#[inline]
+fn truncate_end_index(
+    len: usize,
+    segments: impl Iterator<Item = (usize, usize)>,
+    max_width: usize,
+) -> (usize, usize) {
+    segments
+        // chain a final element representing the position past the last char
+        .chain(core::iter::once((len, 0)))
+        // fold to byte index and the width up to the index
+        .scan(0, |sum: &mut usize, (byte_index, segment_width)| {
+            // byte_index is the start while the segment_width is at the end. Current width is
+            // the sum until now while the next byte_index is including the current
+            // segment_width.
+            let current_width = *sum;
+            *sum = sum.saturating_add(segment_width);
+            Some((byte_index, current_width))
+        })
+        // take the longest but still shorter than requested
+        .take_while(|&(_, current_width)| current_width <= max_width)
+        .last()
+        .unwrap_or((0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use std::borrow::Cow;
+
+    /// Strings of zero total width: ZWSP, combining marks and ZWJ only.
+    const ZERO_WIDTH_ONLY: [&str; 4] = [
+        "\u{200B}\u{200B}",
+        "\u{0301}\u{0306}",
+        "\u{200D}\u{200D}",
+        "\u{200B}\u{200B}\u{0306}",
+    ];
+
+    mod alignment {
+        use super::*;
+
+        #[test]
+        fn opposite() {
+            assert_eq!(Alignment::Left.opposite(), Alignment::Right);
+            assert_eq!(Alignment::Center.opposite(), Alignment::Center);
+            assert_eq!(Alignment::Right.opposite(), Alignment::Left);
+        }
+
+        #[test]
+        fn opposite_twice() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                assert_eq!(align.opposite().opposite(), align);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_owned {
+        use super::*;
+
+        #[test]
+        fn same_as_truncate() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es", "👨‍👩‍👧‍👦"] {
+                for max_width in 0..8 {
+                    let (owned, width) = input.unicode_truncate_owned(max_width);
+                    assert_eq!((owned.as_str(), width), input.unicode_truncate(max_width));
+                }
+            }
+        }
+    }
+
+    mod truncate_end {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            assert_eq!("".unicode_truncate(4), ("", 0));
+        }
+
+        #[test]
+        fn zero_width() {
+            assert_eq!("ab".unicode_truncate(0), ("", 0));
+            assert_eq!("你好".unicode_truncate(0), ("", 0));
+        }
+
+        #[test]
+        fn only_zero_width() {
+            for input in ZERO_WIDTH_ONLY {
+                for max_width in [0, 1, 100] {
+                    assert_eq!(input.unicode_truncate(max_width), (input, 0));
+                }
+            }
+        }
+
+        #[test]
+        fn less_than_limit() {
+            assert_eq!("abc".unicode_truncate(4), ("abc", 3));
+            assert_eq!("你".unicode_truncate(4), ("你", 2));
+        }
+
+        #[test]
+        fn at_boundary() {
+            assert_eq!("boundary".unicode_truncate(5), ("bound", 5));
+            assert_eq!("你好吗".unicode_truncate(4), ("你好", 4));
+        }
+
+        #[test]
+        fn not_boundary() {
+            assert_eq!("你好吗".unicode_truncate(3), ("你", 2));
+            assert_eq!("你好吗".unicode_truncate(1), ("", 0));
+        }
+
+        #[test]
+        fn zero_width_char_in_middle() {
+            // zero width character in the middle is intact
+            assert_eq!("y\u{0306}es".unicode_truncate(2), ("y\u{0306}e", 2));
+        }
+
+        #[test]
+        fn keep_zero_width_char_at_boundary() {
+            // zero width character at end is preserved
+            assert_eq!(
+                "y\u{0306}ey\u{0306}s".unicode_truncate(3),
+                ("y\u{0306}ey\u{0306}", 3)
+            );
+        }
+
+        #[test]
+        fn family_stays_together() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            // Family emoji should be of width 2
+            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
+
+            assert_eq!(input.unicode_truncate(4), ("123", 3));
+            assert_eq!(input.unicode_truncate(5), ("123👨‍👩‍👧‍👦", 5));
+            assert_eq!(input.unicode_truncate(6), ("123👨‍👩‍👧‍👦4", 6));
+            assert_eq!(input.unicode_truncate(20), (input, 8));
+        }
+
+        #[test]
+        fn control_char() {
+            assert_eq!("\u{0019}".unicode_truncate(0), ("", 0));
+            assert_eq!("\u{0019}abc".unicode_truncate(2), ("\u{0019}a", 2));
+            assert_eq!(
+                "\u{0019}\u{0301}a".unicode_truncate(1),
+                ("\u{0019}\u{0301}", 1)
+            );
+            assert_eq!("a\u{0019}\u{0301}".unicode_truncate(1), ("a", 1));
+        }
+
+        #[test]
+        fn emoji_modifier() {
+            assert_eq!("👋🏽abc".unicode_truncate(1), ("", 0));
+            assert_eq!("👋🏽abc".unicode_truncate(2), ("👋🏽", 2));
+            assert_eq!("👋🏽abc".unicode_truncate(3), ("👋🏽a", 3));
+            assert_eq!("a👋🏽".unicode_truncate(2), ("a", 1));
+        }
+
+        #[test]
+        fn keycap_sequence() {
+            // '#', VS16 and the combining enclosing keycap form a single grapheme 2 columns wide
+            let keycap = "#\u{FE0F}\u{20E3}";
+            let input = "#\u{FE0F}\u{20E3}abc";
+            assert_eq!(input.unicode_truncate(1), ("", 0));
+            assert_eq!(input.unicode_truncate(2), (keycap, 2));
+            assert_eq!(input.unicode_truncate(3), ("#\u{FE0F}\u{20E3}a", 3));
+            for max_width in 0..8 {
+                let (result, _) = input.unicode_truncate(max_width);
+                assert_eq!(
+                    result.starts_with(keycap),
+                    result.contains('#'),
+                    "{:?}",
+                    result
+                );
+            }
+        }
+
+        #[test]
+        fn lone_regional_indicator() {
+            // U+1F1FA U+1F1F8 is a flag, and the third regional indicator has no partner
+            let input = "\u{1F1FA}\u{1F1F8}\u{1F1FA}b";
+            assert_eq!("\u{1F1FA}".unicode_truncate(1), ("\u{1F1FA}", 1));
+            assert_eq!(input.unicode_truncate(1), ("", 0));
+            assert_eq!(input.unicode_truncate(2), ("\u{1F1FA}\u{1F1F8}", 2));
+            assert_eq!(
+                input.unicode_truncate(3),
+                ("\u{1F1FA}\u{1F1F8}\u{1F1FA}", 3)
+            );
+            assert_eq!(input.unicode_truncate(4), (input, 4));
+        }
+
+        #[test]
+        fn result_len_not_bounded_by_width() {
+            let input = "a\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}\u{0301}b";
+            assert_eq!(input.unicode_truncate(1).0.len(), 17);
+            let zero_width = "\u{200B}\u{200B}\u{200B}\u{200B}\u{200B}";
+            assert_eq!(zero_width.unicode_truncate(0), (zero_width, 0));
+            assert_eq!("👨‍👩‍👧‍👦".unicode_truncate(2).0.len(), 25);
+        }
+
+        #[test]
+        fn flag() {
+            assert_eq!("🇺🇸abc".unicode_truncate(1), ("", 0));
+            assert_eq!("🇺🇸abc".unicode_truncate(2), ("🇺🇸", 2));
+            assert_eq!("🇺🇸abc".unicode_truncate(3), ("🇺🇸a", 3));
+            assert_eq!("a🇺🇸b".unicode_truncate(2), ("a", 1));
+        }
+
+        #[test]
+        fn flags_never_separated() {
+            let input = "🇺🇸🇩🇪🇯🇵";
+            for max_width in 0..8 {
+                let (result, width) = input.unicode_truncate(max_width);
+                // each regional indicator is 4 bytes long, so whole flags are 8 bytes each
+                assert_eq!(result.len() % 8, 0, "{:?}", result);
+                assert_eq!(width, result.len() / 4);
+                let (result, _) = input.unicode_truncate_start(max_width);
+                assert_eq!(result.len() % 8, 0, "{:?}", result);
+            }
+        }
+
+        #[test]
+        fn zwj_between_graphemes() {
+            // the ZWJ does not join the modifier with the emoji into one grapheme, so the width
+            // is the sum of both even though `str::width` sees an emoji sequence
+            let input = "🏽\u{200D}👋";
+            assert_eq!(input.unicode_truncate(2), ("🏽\u{200D}", 2));
+            assert_eq!(input.unicode_truncate(4), (input, 4));
+            assert_eq!(input.unicode_truncate(20), (input, 4));
+        }
+    }
+
+    mod truncate_utf16 {
+        use super::*;
+
+        #[test]
+        fn same_as_truncate() {
+            for input in [
+                "",
+                "abcdef",
+                "你好吗",
+                "a😀b",
+                "ab\r\ncd",
+                "y\u{0306}es",
+                "\u{200B}\u{200B}",
+                "👨‍👩‍👧‍👦x",
+                "🇺🇸🇩🇪",
+            ] {
+                for max_width in 0..10 {
+                    let (result, width, utf16_len) = input.unicode_truncate_utf16(max_width);
+                    assert_eq!((result, width), input.unicode_truncate(max_width));
+                    assert_eq!(utf16_len, result.encode_utf16().count(), "{:?}", result);
+                }
+            }
+        }
+
+        #[test]
+        fn surrogate_pairs() {
+            assert_eq!("😀😀".unicode_truncate_utf16(3), ("😀", 2, 2));
+            assert_eq!("𝒜bc".unicode_truncate_utf16(2), ("𝒜b", 2, 3));
+        }
+    }
+
+    mod truncate_start {
+        use super::*;
+
+        #[test]
+        fn keycap_sequence() {
+            let keycap = "#\u{FE0F}\u{20E3}";
+            let input = "ab#\u{FE0F}\u{20E3}";
+            assert_eq!(input.unicode_truncate_start(1), ("", 0));
+            assert_eq!(input.unicode_truncate_start(2), (keycap, 2));
+            assert_eq!(input.unicode_truncate_start(3), ("b#\u{FE0F}\u{20E3}", 3));
+            assert_eq!(
+                "abc#\u{FE0F}\u{20E3}".unicode_truncate_start(3),
+                ("c#\u{FE0F}\u{20E3}", 3)
+            );
+            assert_eq!(
+                "abc#\u{FE0F}\u{20E3}".unicode_truncate_start(2),
+                (keycap, 2)
+            );
+            for max_width in 0..8 {
+                let (result, _) = input.unicode_truncate_start(max_width);
+                assert_eq!(
+                    result.ends_with(keycap),
+                    result.contains('\u{FE0F}'),
+                    "{:?}",
+                    result
+                );
+            }
+        }
+
+        #[test]
+        fn lone_regional_indicator() {
+            let input = "\u{1F1FA}\u{1F1F8}\u{1F1FA}b";
+            assert_eq!(input.unicode_truncate_start(1), ("b", 1));
+            assert_eq!(input.unicode_truncate_start(2), ("\u{1F1FA}b", 2));
+            // the flag does not fit, and its second half is not split off
+            assert_eq!(input.unicode_truncate_start(3), ("\u{1F1FA}b", 2));
+            assert_eq!(input.unicode_truncate_start(4), (input, 4));
+            assert_eq!(
+                "a\u{1F1FA}\u{1F1F8}\u{1F1FA}".unicode_truncate_start(1),
+                ("\u{1F1FA}", 1)
+            );
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!("".unicode_truncate_start(4), ("", 0));
+        }
+
+        #[test]
+        fn zero_width() {
+            assert_eq!("ab".unicode_truncate_start(0), ("", 0));
+            assert_eq!("你好".unicode_truncate_start(0), ("", 0));
+        }
+
+        #[test]
+        fn only_zero_width() {
+            for input in ZERO_WIDTH_ONLY {
+                for max_width in [0, 1, 100] {
+                    assert_eq!(input.unicode_truncate_start(max_width), (input, 0));
+                }
+            }
+        }
+
+        #[test]
+        fn zero_width_at_cut_removed() {
+            assert_eq!("a\u{200B}b".unicode_truncate_start(1), ("b", 1));
+            assert_eq!("a\u{200B}\u{200B}b".unicode_truncate_start(1), ("b", 1));
+            assert_eq!("你\u{200D}b".unicode_truncate_start(2), ("b", 1));
+        }
+
+        #[test]
+        fn less_than_limit() {
+            assert_eq!("abc".unicode_truncate_start(4), ("abc", 3));
+            assert_eq!("你".unicode_truncate_start(4), ("你", 2));
+        }
+
+        #[test]
+        fn at_boundary() {
+            assert_eq!("boundary".unicode_truncate_start(5), ("ndary", 5));
+            assert_eq!("你好吗".unicode_truncate_start(4), ("好吗", 4));
+        }
+
+        #[test]
+        fn not_boundary() {
+            assert_eq!("你好吗".unicode_truncate_start(3), ("吗", 2));
+            assert_eq!("你好吗".unicode_truncate_start(1), ("", 0));
+        }
+
+        #[test]
+        fn zero_width_char_in_middle() {
+            // zero width character in middle is preserved
+            assert_eq!(
+                "y\u{0306}ey\u{0306}s".unicode_truncate_start(2),
+                ("y\u{0306}s", 2)
+            );
+        }
+
+        #[test]
+        fn remove_zero_width_char_at_boundary() {
+            // zero width character in the middle at the cutting boundary is removed
+            assert_eq!("y\u{0306}es".unicode_truncate_start(2), ("es", 2));
+        }
+
+        #[test]
+        fn family_stays_together() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            // Family emoji should be of width 2
+            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
+
+            assert_eq!(input.unicode_truncate_start(4), ("456", 3));
+            assert_eq!(input.unicode_truncate_start(5), ("👨‍👩‍👧‍👦456", 5));
+            assert_eq!(input.unicode_truncate_start(6), ("3👨‍👩‍👧‍👦456", 6));
+            assert_eq!(input.unicode_truncate_start(20), (input, 8));
+        }
+
+        #[test]
+        fn control_char() {
+            assert_eq!("\u{0019}".unicode_truncate_start(0), ("", 0));
+            assert_eq!("\u{0019}".unicode_truncate_start(1), ("\u{0019}", 1));
+            assert_eq!("\u{0019}abc".unicode_truncate_start(2), ("bc", 2));
+            assert_eq!("\u{0019}abc".unicode_truncate_start(3), ("abc", 3));
+            assert_eq!("\u{0019}\u{0301}a".unicode_truncate_start(1), ("a", 1));
+            assert_eq!(
+                "a\u{0019}\u{0301}".unicode_truncate_start(1),
+                ("\u{0019}\u{0301}", 1)
+            );
+        }
+
+        #[test]
+        fn base_removed_with_marks() {
+            // the combining mark belongs to the grapheme of its base
+            assert_eq!("e\u{0301}xyz".unicode_truncate_start(3), ("xyz", 3));
+            assert_eq!(
+                "e\u{0301}xyz".unicode_truncate_start(4),
+                ("e\u{0301}xyz", 4)
+            );
+        }
+
+        #[test]
+        fn orphaned_marks_removed() {
+            assert_eq!(
+                "\u{1B}\u{0301}\u{0302}abc".unicode_truncate_start(3),
+                ("abc", 3)
+            );
+            assert_eq!(
+                "\n\u{0301}\u{200D}abc".unicode_truncate_start(3),
+                ("abc", 3)
+            );
+            assert_eq!("\u{1B}\u{0301}".unicode_truncate_start(0), ("", 0));
+            // not truncated
+            assert_eq!("\u{0301}abc".unicode_truncate_start(3), ("\u{0301}abc", 3));
+        }
+
+        #[test]
+        fn zwj_between_graphemes() {
+            let input = "🏽\u{200D}👋";
+            assert_eq!(input.unicode_truncate_start(2), ("👋", 2));
+            assert_eq!(input.unicode_truncate_start(4), (input, 4));
+            assert_eq!(input.unicode_truncate_start(20), (input, 4));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_start_with_base {
+        use super::*;
+
+        #[test]
+        fn base_prepended() {
+            let input = "\u{1B}\u{0301}abc";
+            assert_eq!(
+                input.unicode_truncate_start_with_base(4, ' '),
+                (Cow::Owned(" \u{0301}abc".to_owned()), 4)
+            );
+            assert_eq!(
+                input.unicode_truncate_start_with_base(4, '\u{25CC}'),
+                (Cow::Owned("\u{25CC}\u{0301}abc".to_owned()), 4)
+            );
+        }
+
+        #[test]
+        fn no_marks_at_start() {
+            let input = "\u{1B}\u{0301}abc";
+            assert_eq!(
+                input.unicode_truncate_start_with_base(3, ' '),
+                (Cow::Borrowed("abc"), 3)
+            );
+            assert_eq!(
+                input.unicode_truncate_start_with_base(5, ' '),
+                (Cow::Borrowed(input), 4)
+            );
+            assert_eq!(
+                "你好吗".unicode_truncate_start_with_base(5, ' '),
+                (Cow::Borrowed("好吗"), 4)
+            );
+        }
+
+        #[test]
+        fn base_too_wide() {
+            assert_eq!(
+                "\u{1B}\u{0301}abc".unicode_truncate_start_with_base(0, ' '),
+                (Cow::Borrowed(""), 0)
+            );
+        }
+    }
+
+    mod is_orphaned_mark {
+        use super::*;
+
+        #[test]
+        fn marks() {
+            for grapheme in [
+                "\u{0301}",
+                "\u{0301}\u{0302}",
+                "\u{200D}",
+                "\u{FE0F}",
+                "\u{093F}",
+            ] {
+                assert!(is_orphaned_mark(grapheme), "{:?}", grapheme);
+            }
+        }
+
+        #[test]
+        fn bases() {
+            for grapheme in [
+                "",
+                "a",
+                "e\u{0301}",
+                "你",
+                "\u{1B}",
+                "\r\n",
+                "\u{200B}",
+                "👨‍👩‍👧‍👦",
+            ] {
+                assert!(!is_orphaned_mark(grapheme), "{:?}", grapheme);
+            }
+        }
+    }
+
+    mod truncate_centered {
+        use super::*;
+
+        #[test]
+        fn lone_regional_indicator() {
+            let input = "\u{1F1FA}\u{1F1F8}\u{1F1FA}b";
+            assert_eq!(input.unicode_truncate_centered(1), ("\u{1F1FA}", 1));
+            assert_eq!(
+                input.unicode_truncate_centered(2),
+                ("\u{1F1FA}\u{1F1F8}", 2)
+            );
+            assert_eq!(
+                input.unicode_truncate_centered(3),
+                ("\u{1F1FA}\u{1F1F8}\u{1F1FA}", 3)
+            );
+        }
+
+        #[test]
+        fn regional_indicators_same_width_in_all_directions() {
+            let flags = [
+                "\u{1F1FA}",
+                "\u{1F1FA}\u{1F1F8}",
+                "\u{1F1FA}\u{1F1F8}\u{1F1FA}",
+            ];
+            for (index, input) in flags.iter().enumerate() {
+                // the width of the whole string, measured the same way by each direction
+                let width = index.checked_add(1).unwrap();
+                for truncate in [
+                    <str as UnicodeTruncateStr>::unicode_truncate,
+                    <str as UnicodeTruncateStr>::unicode_truncate_start,
+                    <str as UnicodeTruncateStr>::unicode_truncate_centered,
+                ] {
+                    assert_eq!(truncate(input, width), (*input, width));
+                    for max_width in 0..width {
+                        let (result, result_width) = truncate(input, max_width);
+                        assert_eq!(result_width, graphemes_width(result), "{:?}", result);
+                        assert!(result_width <= max_width);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!("".unicode_truncate_centered(4), ("", 0));
+        }
+
+        #[test]
+        fn zero_width() {
+            assert_eq!("ab".unicode_truncate_centered(0), ("", 0));
+            assert_eq!("你好".unicode_truncate_centered(0), ("", 0));
+        }
+
+        #[test]
+        fn single_grapheme() {
+            assert_eq!("a".unicode_truncate_centered(0), ("", 0));
+            assert_eq!("a".unicode_truncate_centered(1), ("a", 1));
+            assert_eq!("中".unicode_truncate_centered(0), ("", 0));
+            assert_eq!("中".unicode_truncate_centered(1), ("", 0));
+            assert_eq!("中".unicode_truncate_centered(2), ("中", 2));
+            assert_eq!("中".unicode_truncate_centered(3), ("中", 2));
+        }
+
+        #[test]
+        fn only_zero_width() {
+            // the width is 0, so the string fits even in a width of 0
+            let input = "\u{200B}\u{200B}\u{200B}";
+            assert_eq!(input.unicode_truncate_centered(0), (input, 0));
+            assert_eq!(input.unicode_truncate_centered(5), (input, 0));
+            for input in ZERO_WIDTH_ONLY {
+                for max_width in [0, 1, 100] {
+                    assert_eq!(input.unicode_truncate_centered(max_width), (input, 0));
+                }
+            }
+        }
+
+        #[test]
+        fn less_than_limit() {
+            assert_eq!("abc".unicode_truncate_centered(4), ("abc", 3));
+            assert_eq!("你".unicode_truncate_centered(4), ("你", 2));
+        }
+
+        /// The source code has special handling for small `min_removal_width` (half-point)
+        #[test]
+        fn truncate_exactly_one() {
+            assert_eq!("abcd".unicode_truncate_centered(3), ("abc", 3));
+        }
+
+        #[test]
+        fn at_boundary() {
+            assert_eq!(
+                "boundaryboundary".unicode_truncate_centered(5),
+                ("arybo", 5)
+            );
+            assert_eq!(
+                "你好吗你好吗你好吗".unicode_truncate_centered(4),
+                ("你好", 4)
+            );
+        }
+
+        #[test]
+        fn not_boundary() {
+            assert_eq!("你好吗你好吗".unicode_truncate_centered(3), ("吗", 2));
+            assert_eq!("你好吗你好吗".unicode_truncate_centered(1), ("", 0));
+        }
+
+        #[test]
+        fn zero_width_char_in_middle() {
+            // zero width character in middle is preserved
+            assert_eq!(
+                "yy\u{0306}es".unicode_truncate_centered(2),
+                ("y\u{0306}e", 2)
+            );
+        }
+
+        #[test]
+        fn zero_width_char_at_boundary() {
+            // zero width character at the cutting boundary in the start is removed
+            // but those in the end is kept.
+            assert_eq!(
+                "y\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}"
+                    .unicode_truncate_centered(2),
+                ("b\u{0306}y\u{0306}", 2)
+            );
+            assert_eq!(
+                "ay\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}"
+                    .unicode_truncate_centered(2),
+                ("a\u{0306}b\u{0306}", 2)
+            );
+            assert_eq!(
+                "y\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}a"
+                    .unicode_truncate_centered(2),
+                ("b\u{0306}y\u{0306}", 2)
+            );
+        }
+
+        #[test]
+        fn control_char() {
+            use unicode_width::UnicodeWidthChar;
+            assert_eq!("\u{0019}".width(), 1);
+            assert_eq!('\u{0019}'.width(), None);
+            assert_eq!("\u{0019}".unicode_truncate(2), ("\u{0019}", 1));
+        }
+
+        #[test]
+        fn control_char_removed() {
+            assert_eq!("\u{0019}".unicode_truncate_centered(0), ("", 0));
+            assert_eq!("\u{0019}abc".unicode_truncate_centered(2), ("ab", 2));
+            assert_eq!(
+                "\u{0019}abc".unicode_truncate_centered(3),
+                ("\u{0019}ab", 3)
+            );
+            assert_eq!(
+                "\u{0019}\u{0301}a".unicode_truncate_centered(1),
+                ("\u{0019}\u{0301}", 1)
+            );
+        }
+
+        #[test]
+        fn c1_control_char() {
+            assert_eq!("\u{85}a\u{85}".unicode_truncate_centered(1), ("a", 1));
+            assert_eq!(
+                "a\u{9F}\u{0301}b".unicode_truncate_centered(2),
+                ("a\u{9F}\u{0301}", 2)
+            );
+        }
+
+        #[test]
+        fn zwj_between_graphemes() {
+            let input = "🏽\u{200D}👋";
+            // the modifier has no base of its own, so the result does not start with it
+            assert_eq!(input.unicode_truncate_centered(2), ("👋", 2));
+            assert_eq!(input.unicode_truncate_centered(3), ("👋", 2));
+            assert_eq!(input.unicode_truncate_centered(4), (input, 4));
+            assert_eq!(input.unicode_truncate_centered(20), (input, 4));
+        }
+
+        #[test]
+        fn removed_from_start_only() {
+            assert_eq!("a你".unicode_truncate_centered(2), ("你", 2));
+            assert_eq!("你a".unicode_truncate_centered(2), ("你", 2));
+        }
+
+        #[test]
+        fn family_stays_together() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            // Family emoji should be of width 2
+            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
+
+            // the widest result is off center
+            assert_eq!(input.unicode_truncate_centered(1), ("3", 1));
+            assert_eq!(input.unicode_truncate_centered(2), ("👨‍👩‍👧‍👦", 2));
+            assert_eq!(input.unicode_truncate_centered(4), ("3👨‍👩‍👧‍👦4", 4));
+            assert_eq!(input.unicode_truncate_centered(6), ("23👨‍👩‍👧‍👦45", 6));
+            assert_eq!(input.unicode_truncate_centered(20), (input, 8));
+        }
+    }
+
+    mod truncate_centered_with_center {
+        use super::*;
+
+        #[test]
+        fn same_as_centered() {
+            for input in ["", "abcdef", "你好吗", "a你b\u{0306}c", "\u{200B}"] {
+                for max_width in 0..8 {
+                    let (result, width, _) = input.unicode_truncate_centered_with_center(max_width);
+                    assert_eq!((result, width), input.unicode_truncate_centered(max_width));
+                }
+            }
+        }
+
+        #[test]
+        fn odd_and_even_width() {
+            assert_eq!("abc".unicode_truncate_centered_with_center(3).2, "b");
+            assert_eq!("ab".unicode_truncate_centered_with_center(2).2, "a");
+            assert_eq!("a".unicode_truncate_centered_with_center(1).2, "a");
+        }
+
+        #[test]
+        fn wide_chars() {
+            assert_eq!("你好".unicode_truncate_centered_with_center(4).2, "你");
+            assert_eq!("a你b".unicode_truncate_centered_with_center(4).2, "你");
+            assert_eq!("你好吗".unicode_truncate_centered_with_center(6).2, "好");
+        }
+
+        #[test]
+        fn zero_width_never_center() {
+            assert_eq!("a\u{200B}b".unicode_truncate_centered_with_center(2).2, "a");
+            assert_eq!(
+                "\u{200B}\u{200B}".unicode_truncate_centered_with_center(0),
+                ("\u{200B}\u{200B}", 0, "")
+            );
+            assert_eq!(
+                "ey\u{0306}s".unicode_truncate_centered_with_center(3).2,
+                "y\u{0306}"
+            );
+        }
+    }
+
+    #[test]
+    fn truncate_aligned() {
+        assert_eq!("abc".unicode_truncate_aligned(1, Alignment::Left), ("a", 1));
+        assert_eq!(
+            "abc".unicode_truncate_aligned(1, Alignment::Center),
+            ("b", 1)
+        );
+        assert_eq!(
+            "abc".unicode_truncate_aligned(1, Alignment::Right),
+            ("c", 1)
+        );
+    }
+
+    mod truncate_centered_wide_grapheme {
+        use super::*;
+
+        /// Ten waving hands joined by ZWJ, a single grapheme 20 columns wide when summed.
+        macro_rules! chain {
+            () => {
+                "👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋\u{200D}👋"
+            };
+        }
+
+        fn truncate(input: &str, max_width: usize) -> (&str, usize) {
+            input.unicode_truncate_with_zwj_policy(max_width, Alignment::Center, ZwjWidth::Sum)
+        }
+
+        /// The width of the widest slice at grapheme boundaries that fits in `max_width`.
+        fn widest(input: &str, max_width: usize) -> usize {
+            let boundaries = || {
+                input
+                    .grapheme_indices(true)
+                    .map(|(byte_index, _)| byte_index)
+                    .chain(core::iter::once(input.len()))
+            };
+            let mut widest = 0;
+            for start in boundaries() {
+                for end in boundaries().filter(|&end| end >= start) {
+                    let width = input[start..end]
+                        .graphemes(true)
+                        .map(|grapheme| ZwjWidth::Sum.grapheme_width(grapheme))
+                        .fold(0, usize::saturating_add);
+                    if width <= max_width {
+                        widest = widest.max(width);
+                    }
+                }
+            }
+            widest
+        }
+
+        #[test]
+        fn is_widest() {
+            assert_eq!(ZwjWidth::Sum.grapheme_width(chain!()), 20);
+            for input in [
+                concat!("abcdefghij", chain!(), "klmnopqrst"),
+                concat!(chain!(), "abcdefghijklmnopqrst"),
+                concat!("abcdefghijklmnopqrst", chain!()),
+                concat!("abcde", chain!(), "fghijklmnopqrst"),
+                concat!("abcdefghijklmno", chain!(), "pqrst"),
+            ] {
+                for max_width in 0..42 {
+                    let (result, width) = truncate(input, max_width);
+                    assert!(input.contains(result));
+                    assert_eq!(
+                        width,
+                        widest(input, max_width),
+                        "{:?} at {}",
+                        input,
+                        max_width
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn at_center() {
+            let input = concat!("abcdefghij", chain!(), "klmnopqrst");
+            assert_eq!(truncate(input, 24), (concat!("ij", chain!(), "kl"), 24));
+            assert_eq!(truncate(input, 15), ("abcdefghij", 10));
+        }
+
+        #[test]
+        fn at_edges() {
+            let input = concat!(chain!(), "abcdefghijklmnopqrst");
+            // removing either side is equally balanced, so the end is removed
+            assert_eq!(truncate(input, 20), (chain!(), 20));
+            assert_eq!(truncate(input, 19), ("abcdefghijklmnopqrs", 19));
+
+            let input = concat!("abcdefghijklmnopqrst", chain!());
+            assert_eq!(truncate(input, 20), ("abcdefghijklmnopqrst", 20));
+            assert_eq!(truncate(input, 19), ("bcdefghijklmnopqrst", 19));
+        }
+
+        #[test]
+        fn spanning_midpoint() {
+            let input = concat!("abcde", chain!(), "fghijklmnopqrst");
+            assert_eq!(truncate(input, 24), (concat!(chain!(), "fghi"), 24));
+            assert_eq!(truncate(input, 19), ("fghijklmnopqrst", 15));
+        }
+    }
+
+    mod truncate_last_grapheme {
+        use super::*;
+
+        #[test]
+        fn nothing_fits() {
+            assert_eq!("".unicode_truncate_last_grapheme(4), None);
+            assert_eq!("ab".unicode_truncate_last_grapheme(0), None);
+            assert_eq!("你好".unicode_truncate_last_grapheme(1), None);
+        }
+
+        #[test]
+        fn last_kept() {
+            assert_eq!("abc".unicode_truncate_last_grapheme(4), Some((2..3, "c")));
+            assert_eq!(
+                "你好吗".unicode_truncate_last_grapheme(3),
+                Some((0..3, "你"))
+            );
+            assert_eq!(
+                "123👨‍👩‍👧‍👦456".unicode_truncate_last_grapheme(5),
+                Some((3..28, "👨‍👩‍👧‍👦"))
+            );
+        }
+
+        #[test]
+        fn zero_width_at_boundary() {
+            assert_eq!(
+                "y\u{0306}es".unicode_truncate_last_grapheme(1),
+                Some((0..3, "y\u{0306}"))
+            );
+            assert_eq!(
+                "ab\u{200B}c".unicode_truncate_last_grapheme(2),
+                Some((2..5, "\u{200B}"))
+            );
+        }
+
+        #[test]
+        fn regional_indicators() {
+            // the pairing of regional indicators depends on all the preceding ones
+            assert_eq!(
+                "🇺🇸🇺🇸🇺".unicode_truncate_last_grapheme(4),
+                Some((8..16, "🇺🇸"))
+            );
+        }
+    }
+
+    mod truncate_around {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            assert_eq!("".unicode_truncate_around(0, 4), ("", 0));
+        }
+
+        #[test]
+        fn fits() {
+            assert_eq!("abc".unicode_truncate_around(1, 4), ("abc", 3));
+            assert_eq!("你好".unicode_truncate_around(3, 4), ("你好", 4));
+        }
+
+        #[test]
+        fn centered_on_pivot() {
+            let input = "abcdefghij";
+            assert_eq!(input.unicode_truncate_around(0, 3), ("abc", 3));
+            assert_eq!(input.unicode_truncate_around(4, 3), ("def", 3));
+            // ties are broken towards the end
+            assert_eq!(input.unicode_truncate_around(4, 4), ("defg", 4));
+            assert_eq!(input.unicode_truncate_around(9, 3), ("hij", 3));
+            // past the end refers to the last grapheme
+            assert_eq!(input.unicode_truncate_around(20, 2), ("ij", 2));
+        }
+
+        #[test]
+        fn pivot_inside_grapheme() {
+            assert_eq!("你好吗".unicode_truncate_around(4, 2), ("好", 2));
+            assert_eq!(
+                "ay\u{0306}b".unicode_truncate_around(2, 1),
+                ("y\u{0306}", 1)
+            );
+        }
+
+        #[test]
+        fn pivot_too_wide() {
+            assert_eq!("a你b".unicode_truncate_around(1, 1), ("", 0));
+        }
+
+        #[test]
+        fn other_side_grows_when_blocked() {
+            assert_eq!("你ab".unicode_truncate_around(3, 3), ("ab", 2));
+            assert_eq!("你abc".unicode_truncate_around(3, 3), ("abc", 3));
+            assert_eq!("abc你d".unicode_truncate_around(2, 2), ("bc", 2));
+            assert_eq!("abc你d".unicode_truncate_around(2, 3), ("c你", 3));
+        }
+
+        #[test]
+        fn zero_width_kept() {
+            assert_eq!(
+                "a\u{200B}b\u{200B}c".unicode_truncate_around(4, 1),
+                ("\u{200B}b\u{200B}", 1)
+            );
+        }
+    }
+
+    mod truncate_with_zwj_policy {
+        use super::*;
+
+        fn truncate(input: &str, max_width: usize, align: Alignment) -> [(&str, usize); 2] {
+            [ZwjWidth::Sum, ZwjWidth::Single]
+                .map(|policy| input.unicode_truncate_with_zwj_policy(max_width, align, policy))
+        }
+
+        #[test]
+        fn plain_text_unaffected() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for max_width in 0..8 {
+                    let expected = "你好吗".unicode_truncate_aligned(max_width, align);
+                    assert_eq!(truncate("你好吗", max_width, align), [expected; 2]);
+                }
+            }
+        }
+
+        #[test]
+        fn family_stays_together_end() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            assert_eq!(
+                truncate(input, 4, Alignment::Left),
+                [("123", 3), ("123", 3)]
+            );
+            assert_eq!(
+                truncate(input, 5, Alignment::Left),
+                [("123", 3), ("123👨‍👩‍👧‍👦", 5)]
+            );
+            assert_eq!(
+                truncate(input, 11, Alignment::Left),
+                [("123👨‍👩‍👧‍👦", 11), ("123👨‍👩‍👧‍👦456", 8)]
+            );
+            assert_eq!(
+                truncate(input, 20, Alignment::Left),
+                [(input, 14), (input, 8)]
+            );
+        }
+
+        #[test]
+        fn family_stays_together_start() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            assert_eq!(
+                truncate(input, 4, Alignment::Right),
+                [("456", 3), ("456", 3)]
+            );
+            assert_eq!(
+                truncate(input, 5, Alignment::Right),
+                [("456", 3), ("👨‍👩‍👧‍👦456", 5)]
+            );
+            assert_eq!(
+                truncate(input, 11, Alignment::Right),
+                [("👨‍👩‍👧‍👦456", 11), ("123👨‍👩‍👧‍👦456", 8)]
+            );
+            assert_eq!(
+                truncate(input, 20, Alignment::Right),
+                [(input, 14), (input, 8)]
+            );
+        }
+
+        #[test]
+        fn family_stays_together_centered() {
+            let input = "123👨‍👩‍👧‍👦456";
+
+            assert_eq!(
+                truncate(input, 2, Alignment::Center),
+                [("23", 2), ("👨‍👩‍👧‍👦", 2)]
+            );
+            assert_eq!(
+                truncate(input, 8, Alignment::Center),
+                [("👨‍👩‍👧‍👦", 8), (input, 8)]
+            );
+            assert_eq!(
+                truncate(input, 10, Alignment::Center),
+                [("3👨‍👩‍👧‍👦4", 10), (input, 8)]
+            );
+            assert_eq!(
+                truncate(input, 20, Alignment::Center),
+                [(input, 14), (input, 8)]
+            );
+        }
+    }
+
+    mod truncate_at {
+        use super::*;
+
+        #[test]
+        fn wide_segments() {
+            let input = "你好/世界/吗";
+            assert_eq!(
+                input.unicode_truncate_at(9, &['/'], false),
+                ("你好/世界", 9)
+            );
+            assert_eq!(input.unicode_truncate_at(8, &['/'], false), ("你好", 4));
+            assert_eq!(input.unicode_truncate_at(8, &['/'], true), ("你好", 4));
+        }
+
+        #[test]
+        fn breakpoint_in_grapheme() {
+            // the combining mark makes the breakpoint part of the grapheme before it
+            let input = "ab\u{301}:cd";
+            assert_eq!(
+                input.unicode_truncate_at(4, &[':'], false),
+                ("ab\u{301}", 2)
+            );
+            assert_eq!(input.unicode_truncate_at(4, &['\u{301}'], false), ("", 0));
+        }
+    }
+
+    mod truncate_smart {
+        use super::*;
+
+        #[test]
+        fn fits() {
+            let config = SmartTruncateConfig::default();
+            assert_eq!(
+                "hello world".unicode_truncate_smart(11, config),
+                ("hello world", 11)
+            );
+            assert_eq!("".unicode_truncate_smart(4, config), ("", 0));
+        }
+
+        #[test]
+        fn word_boundary() {
+            let config = SmartTruncateConfig::default();
+            assert_eq!(
+                "hello world".unicode_truncate_smart(6, config),
+                ("hello", 5)
+            );
+            assert_eq!(
+                "hello world".unicode_truncate_smart(5, config),
+                ("hello", 5)
+            );
+            assert_eq!(
+                "one two three four".unicode_truncate_smart(16, config),
+                ("one two three", 13)
+            );
+        }
+
+        #[test]
+        fn fallback_to_exact() {
+            let config = SmartTruncateConfig::default();
+            assert_eq!(
+                "hello world".unicode_truncate_smart(8, config),
+                ("hello wo", 8)
+            );
+            assert_eq!("helloworld".unicode_truncate_smart(4, config), ("hell", 4));
+
+            let config = SmartTruncateConfig {
+                min_acceptable_fraction: 0.5,
+                ..config
+            };
+            assert_eq!(
+                "hello world".unicode_truncate_smart(8, config),
+                ("hello", 5)
+            );
+        }
+
+        #[test]
+        fn exact_when_not_preferred() {
+            let config = SmartTruncateConfig {
+                prefer_word_boundary: false,
+                ..SmartTruncateConfig::default()
+            };
+            assert_eq!(
+                "hello world".unicode_truncate_smart(6, config),
+                ("hello ", 6)
+            );
+        }
+
+        #[test]
+        fn wide_chars() {
+            // ideographs are words on their own
+            let config = SmartTruncateConfig::default();
+            assert_eq!("你好吗".unicode_truncate_smart(5, config), ("你好", 4));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod write_fit {
+        use super::*;
+
+        /// Counts the calls to `write`.
+        struct CountingWriter {
+            buf: Vec<u8>,
+            writes: usize,
+        }
+
+        impl std::io::Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.writes = self.writes.checked_add(1).unwrap();
+                self.buf.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        fn write_fit(s: &str, width: usize, align: Alignment, fill: char) -> String {
+            let mut out = Vec::new();
+            s.unicode_write_fit(width, align, fill, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        }
+
+        #[test]
+        fn same_as_pad() {
+            for input in ["", "abc", "你好吗", "123👨‍👩‍👧‍👦456"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for width in 0..12 {
+                        assert_eq!(
+                            write_fit(input, width, align, ' '),
+                            input.unicode_pad(width, align, true)
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn fill() {
+            assert_eq!(write_fit("你好吗", 5, Alignment::Center, '.'), "你好.");
+            assert_eq!(write_fit("ab", 5, Alignment::Center, '·'), "·ab··");
+        }
+
+        #[test]
+        fn wide_fill() {
+            assert_eq!(write_fit("你好吗", 5, Alignment::Center, '＊'), "你好 ");
+            assert_eq!(write_fit("ab", 8, Alignment::Center, '＊'), " ＊ab＊ ");
+            assert_eq!(write_fit("ab", 6, Alignment::Right, '＊'), "＊＊ab");
+        }
+
+        #[test]
+        fn padding_in_chunks() {
+            let mut out = CountingWriter {
+                buf: Vec::new(),
+                writes: 0,
+            };
+            "ab".unicode_write_fit(202, Alignment::Left, '-', &mut out)
+                .unwrap();
+            // the content and four chunks of padding
+            assert_eq!(out.writes, 5);
+            assert_eq!(out.buf.len(), 202);
+
+            let mut out = CountingWriter {
+                buf: Vec::new(),
+                writes: 0,
+            };
+            "ab".unicode_write_fit(66, Alignment::Left, '·', &mut out)
+                .unwrap();
+            // the content and two chunks of 32 two-byte characters
+            assert_eq!(out.writes, 3);
+            assert_eq!(out.buf.len(), 2 + 64 * 2);
+        }
+    }
+
+    #[cfg(feature = "compact")]
+    mod truncate_compact {
+        use super::*;
+
+        #[test]
+        fn same_as_truncate_for_ascii_and_cjk() {
+            for input in [
+                "",
+                "boundary",
+                "你好吗",
+                "ab你好c",
+                "a\u{0301}b你",
+                "a\r\nb\u{1B}",
+            ] {
+                for max_width in 0..12 {
+                    assert_eq!(
+                        input.unicode_truncate_compact(max_width),
+                        input.unicode_truncate(max_width)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn narrow_non_ascii_overestimated() {
+            assert_eq!("→→→".unicode_truncate_compact(4), ("→→", 4));
+            assert_eq!("→→→".unicode_truncate(4), ("→→→", 3));
+        }
+    }
+
+    mod truncate_strict_width {
+        use super::*;
+
+        #[test]
+        fn known_width() {
+            assert_eq!("你好吗".unicode_truncate_strict_width(5), Ok(("你好", 4)));
+            assert_eq!(
+                "e\u{301}\u{200B}x".unicode_truncate_strict_width(2),
+                Ok(("e\u{301}\u{200B}x", 2))
+            );
+        }
+
+        #[test]
+        fn control_char_in_result() {
+            for input in ["a\nb", "a\r\nb", "a\u{0}", "a\u{85}b"] {
+                let err = input.unicode_truncate_strict_width(4).unwrap_err();
+                assert_eq!(err.offset(), 1, "{:?}", input);
+                assert_eq!(Some(err.character()), input[1..].chars().next());
+            }
+        }
+
+        #[test]
+        fn control_char_cut_off() {
+            assert_eq!(
+                "你好\u{1B}[0m".unicode_truncate_strict_width(4),
+                Ok(("你好", 4))
+            );
+        }
+    }
+
+    mod truncate_trim_trailing {
+        use super::*;
+
+        #[test]
+        fn multiple_spaces() {
+            assert_eq!(
+                "hello   world".unicode_truncate_trim_trailing(8),
+                ("hello", 5)
+            );
+            assert_eq!(
+                "hello   world".unicode_truncate_trim_trailing(6),
+                ("hello", 5)
+            );
+            assert_eq!(
+                "hello   world".unicode_truncate_trim_trailing(9),
+                ("hello   w", 9)
+            );
+        }
+
+        #[test]
+        fn ideographic_space() {
+            assert_eq!(
+                "你好\u{3000}吗".unicode_truncate_trim_trailing(6),
+                ("你好", 4)
+            );
+            assert_eq!(
+                "你好\u{3000}\u{3000}吗".unicode_truncate_trim_trailing(7),
+                ("你好", 4)
+            );
+            assert_eq!(
+                "你好 \u{3000}\t吗".unicode_truncate_trim_trailing(8),
+                ("你好", 4)
+            );
+        }
+
+        #[test]
+        fn only_whitespace() {
+            assert_eq!("   ".unicode_truncate_trim_trailing(2), ("", 0));
+            assert_eq!(
+                "\u{3000}\u{3000}".unicode_truncate_trim_trailing(4),
+                ("", 0)
+            );
+        }
+
+        #[test]
+        fn leading_whitespace_kept() {
+            assert_eq!("  ab  ".unicode_truncate_trim_trailing(5), ("  ab", 4));
+        }
+
+        #[test]
+        fn mark_on_space_kept() {
+            // the combining mark makes the space visible
+            assert_eq!(
+                "a \u{0301}bc".unicode_truncate_trim_trailing(2),
+                ("a \u{0301}", 2)
+            );
+        }
+    }
+
+    mod truncate_full {
+        use super::*;
+
+        #[test]
+        fn untouched() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                assert_eq!(
+                    "你好".unicode_truncate_aligned_full(5, align),
+                    ("你好", 4, FitOutcome::Untouched)
+                );
+                assert_eq!(
+                    "".unicode_truncate_aligned_full(0, align),
+                    ("", 0, FitOutcome::Untouched)
+                );
+            }
+        }
+
+        #[test]
+        fn exact() {
+            assert_eq!(
+                "abcdef".unicode_truncate_full(4),
+                ("abcd", 4, FitOutcome::Exact)
+            );
+            assert_eq!(
+                "abcdef".unicode_truncate_start_full(4),
+                ("cdef", 4, FitOutcome::Exact)
+            );
+            assert_eq!(
+                "abcdef".unicode_truncate_centered_full(4),
+                ("bcde", 4, FitOutcome::Exact)
+            );
+            assert_eq!("ab".unicode_truncate_full(0), ("", 0, FitOutcome::Exact));
+        }
+
+        #[test]
+        fn short() {
+            let short = FitOutcome::Short { unusable_cols: 1 };
+            assert_eq!("你好吗".unicode_truncate_full(5), ("你好", 4, short));
+            assert_eq!("你好吗".unicode_truncate_start_full(5), ("好吗", 4, short));
+            assert_eq!(
+                "你好吗".unicode_truncate_centered_full(5),
+                ("你好", 4, short)
+            );
+            assert_eq!(
+                "你好吗".unicode_truncate_full(1),
+                ("", 0, FitOutcome::Short { unusable_cols: 1 })
+            );
+        }
+
+        #[test]
+        fn same_as_tuple() {
+            for input in ["", "abc", "你好吗", "123👨‍👩‍👧‍👦456", "a\u{0301}你\u{200B}"]
+            {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for max_width in 0..12 {
+                        let (result, width, outcome) =
+                            input.unicode_truncate_aligned_full(max_width, align);
+                        assert_eq!(
+                            (result, width),
+                            input.unicode_truncate_aligned(max_width, align)
+                        );
+                        assert_eq!(
+                            width.saturating_add(outcome.unusable_cols()),
+                            if result == input { width } else { max_width }
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    mod truncate_fraction {
+        use super::*;
+
+        #[test]
+        fn rounded_down() {
+            assert_eq!(fraction_of_columns(300, 0.333), 99);
+            assert_eq!(fraction_of_columns(3, 0.333), 0);
+            assert_eq!(fraction_of_columns(3, 0.334), 1);
+            assert_eq!(fraction_of_columns(80, 0.5), 40);
+            assert_eq!(fraction_of_columns(81, 0.5), 40);
+            assert_eq!(fraction_of_columns(100, 0.29), 29);
+            assert_eq!(fraction_of_columns(3, 1.0 / 3.0), 1);
+        }
+
+        #[test]
+        fn clamped() {
+            assert_eq!(fraction_of_columns(80, 0.0), 0);
+            assert_eq!(fraction_of_columns(80, -0.5), 0);
+            assert_eq!(fraction_of_columns(80, f32::NAN), 0);
+            assert_eq!(fraction_of_columns(80, 1.0), 80);
+            assert_eq!(fraction_of_columns(80, 1.5), 80);
+            assert_eq!(fraction_of_columns(80, f32::INFINITY), 80);
+            assert_eq!(fraction_of_columns(0, 0.5), 0);
+        }
+
+        #[test]
+        fn truncated() {
+            assert_eq!("你好吗".unicode_truncate_fraction(10, 0.5), ("你好", 4));
+            assert_eq!("你好吗".unicode_truncate_fraction(10, 0.3), ("你", 2));
+            assert_eq!("abc".unicode_truncate_fraction(10, 2.0), ("abc", 3));
+        }
+    }
+
+    mod truncate_range {
+        use super::*;
+
+        #[test]
+        fn same_as_slices() {
+            for input in [
+                "",
+                "你好吗",
+                "123👨‍👩‍👧‍👦456",
+                "y\u{0306}ey\u{0306}s",
+                "\u{200B}a\u{0301}你\u{200B}",
+            ] {
+                for max_width in 0..12 {
+                    let (range, width) = input.unicode_truncate_range(max_width);
+                    assert_eq!((&input[range], width), input.unicode_truncate(max_width));
+                    let (range, width) = input.unicode_truncate_start_range(max_width);
+                    assert_eq!(
+                        (&input[range], width),
+                        input.unicode_truncate_start(max_width)
+                    );
+                    let (range, width) = input.unicode_truncate_centered_range(max_width);
+                    assert_eq!(
+                        (&input[range], width),
+                        input.unicode_truncate_centered(max_width)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn empty_at_edges() {
+            assert_eq!("你好".unicode_truncate_range(1), (0..0, 0));
+            assert_eq!("你好".unicode_truncate_start_range(1), (6..6, 0));
+            assert_eq!("你好".unicode_truncate_centered_range(0), (0..0, 0));
+        }
+
+        #[test]
+        fn centered_in_middle() {
+            assert_eq!("abcdef".unicode_truncate_centered_range(2), (2..4, 2));
+            assert_eq!("abcdef".unicode_truncate_centered_range(6), (0..6, 6));
+        }
+    }
+
+    mod truncate_with_model {
+        use super::*;
+
+        #[test]
+        fn unicode_width_model_same_as_aligned() {
+            for input in ["你好吗", "123👨‍👩‍👧‍👦456", "a\u{2764}\u{FE0F}b\u{AD}c"]
+            {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for max_width in 0..12 {
+                        assert_eq!(
+                            input.unicode_truncate_with_model(max_width, align, &UnicodeWidthModel),
+                            input.unicode_truncate_aligned(max_width, align)
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn musl() {
+            let input = "ab\u{AD}cd";
+            assert_eq!(input.unicode_truncate(3), ("ab\u{AD}c", 3));
+            assert_eq!(
+                input.unicode_truncate_with_model(3, Alignment::Left, &MuslWcwidthModel),
+                ("ab\u{AD}", 3)
+            );
+            assert_eq!(
+                input.unicode_truncate_with_model(3, Alignment::Right, &MuslWcwidthModel),
+                ("\u{AD}cd", 3)
+            );
+        }
+
+        /// Measures `w` as wide as possible, so that sums of widths overflow.
+        struct HugeModel;
+
+        impl WidthModel for HugeModel {
+            fn grapheme_width(&self, grapheme: &str) -> usize {
+                if grapheme == "w" {
+                    usize::MAX
+                } else {
+                    1
+                }
+            }
+        }
+
+        #[test]
+        fn overflow_saturates() {
+            let input = "abwc";
+            assert_eq!(
+                input.unicode_truncate_with_model(5, Alignment::Left, &HugeModel),
+                ("ab", 2)
+            );
+            assert_eq!(
+                input.unicode_truncate_with_model(5, Alignment::Right, &HugeModel),
+                ("c", 1)
+            );
+            assert_eq!(
+                "wxw".unicode_truncate_with_model(usize::MAX, Alignment::Left, &HugeModel),
+                ("wxw", usize::MAX)
+            );
+            assert_eq!(
+                "wxw".unicode_truncate_with_model(usize::MAX, Alignment::Right, &HugeModel),
+                ("wxw", usize::MAX)
+            );
+        }
+
+        #[test]
+        fn overflow_centered_exact() {
+            assert_eq!(
+                "awb".unicode_truncate_with_model(usize::MAX, Alignment::Center, &HugeModel),
+                ("w", usize::MAX)
+            );
+            assert_eq!(
+                "abwwcd".unicode_truncate_with_model(3, Alignment::Center, &HugeModel),
+                ("ab", 2)
+            );
+            assert_eq!(
+                "wxw".unicode_truncate_with_model(usize::MAX, Alignment::Center, &HugeModel),
+                ("w", usize::MAX)
+            );
+        }
+
+        #[test]
+        fn zwj_width_as_model() {
+            let input = "123👨‍👩‍👧‍👦456";
+            for policy in [ZwjWidth::Sum, ZwjWidth::Single] {
+                for max_width in 0..16 {
+                    assert_eq!(
+                        input.unicode_truncate_with_model(max_width, Alignment::Left, &policy),
+                        input.unicode_truncate_with_zwj_policy(max_width, Alignment::Left, policy)
+                    );
+                }
+            }
+        }
+    }
+
+    mod truncate_units {
+        use super::*;
+
+        /// Treats `[tag]...[/tag]` as one unit, everything else is split into graphemes.
+        fn markup_units(s: &str) -> impl Iterator<Item = (usize, &str)> {
+            let mut rest = s;
+            core::iter::from_fn(move || {
+                let start = s.len().checked_sub(rest.len()).unwrap();
+                let markup_len = rest
+                    .strip_prefix('[')
+                    .and_then(|_| rest.find("[/"))
+                    .and_then(|close| {
+                        let end = rest[close..].find(']')?;
+                        close.checked_add(end)?.checked_add(1)
+                    });
+                let len = markup_len.or_else(|| rest.graphemes(true).next().map(str::len))?;
+                let (unit, remaining) = rest.split_at(len);
+                rest = remaining;
+                Some((start, unit))
+            })
+        }
+
+        #[test]
+        fn empty() {
+            assert_eq!("".unicode_truncate_units(4, markup_units), ("", 0));
+        }
+
+        #[test]
+        fn graphemes_as_units() {
+            let graphemes = |s| UnicodeSegmentation::grapheme_indices(s, true);
+            for input in ["你好吗", "y\u{0306}ey\u{0306}s", "123👨‍👩‍👧‍👦456"] {
+                for max_width in 0..10 {
+                    assert_eq!(
+                        input.unicode_truncate_units(max_width, graphemes),
+                        input.unicode_truncate(max_width)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn unit_stays_together() {
+            let input = "ab[c]red[/c]d";
+            assert_eq!(input.unicode_truncate_units(2, markup_units), ("ab", 2));
+            assert_eq!(input.unicode_truncate_units(11, markup_units), ("ab", 2));
+            assert_eq!(
+                input.unicode_truncate_units(12, markup_units),
+                ("ab[c]red[/c]", 12)
+            );
+            assert_eq!(input.unicode_truncate_units(20, markup_units), (input, 13));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_bidi_safe {
+        use super::*;
+
+        #[test]
+        fn nested_isolates() {
+            let input = "a\u{2067}b\u{2066}cd\u{2069}e\u{2069}f";
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(3, Alignment::Left),
+                ("a\u{2067}b\u{2066}c\u{2069}\u{2069}".into(), 3)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(5, Alignment::Left),
+                ("a\u{2067}b\u{2066}cd\u{2069}e\u{2069}".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(2, Alignment::Right),
+                ("ef".into(), 2)
+            );
+            // the zero-width isolate opener at the start is removed by truncation, and with it the
+            // terminator
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(4, Alignment::Right),
+                ("cdef".into(), 4)
+            );
+            // zero-width isolate openers at the start are removed by truncation
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(2, Alignment::Center),
+                ("cd".into(), 2)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(4, Alignment::Center),
+                ("b\u{2066}cd\u{2069}e".into(), 4)
+            );
+        }
+
+        #[test]
+        fn untruncated_unbalanced() {
+            let input = "a\u{2067}b\u{2069}\u{2069}c\u{2066}";
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                assert!(matches!(
+                    input.unicode_truncate_bidi_safe(3, align),
+                    (Cow::Borrowed(result), 3) if result == input
+                ));
+            }
+        }
+
+        #[test]
+        fn cut_on_terminator() {
+            let input = "a\u{2068}bc\u{2069}d";
+            // trailing zero-width terminator is kept by end truncation
+            assert!(matches!(
+                input.unicode_truncate_bidi_safe(3, Alignment::Left),
+                (Cow::Borrowed("a\u{2068}bc\u{2069}"), 3)
+            ));
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(2, Alignment::Left),
+                ("a\u{2068}b\u{2069}".into(), 2)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(1, Alignment::Right),
+                ("d".into(), 1)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(2, Alignment::Right),
+                ("cd".into(), 2)
+            );
+        }
+
+        #[test]
+        fn embeddings() {
+            let input = "\u{202B}ab\u{202C}c";
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(1, Alignment::Left),
+                ("\u{202B}a\u{202C}".into(), 1)
+            );
+            assert_eq!(
+                input.unicode_truncate_bidi_safe(2, Alignment::Right),
+                ("bc".into(), 2)
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad {
+        use super::*;
+
+        #[test]
+        fn no_truncate_pads_by_grapheme_width() {
+            // `str::width` sees an emoji sequence 2 columns wide, but the graphemes take 4
+            let input = "🏽\u{200D}👋";
+            assert_eq!(input.width(), 2);
+            assert_eq!(
+                input.unicode_pad(6, Alignment::Left, false),
+                "🏽\u{200D}👋  "
+            );
+            assert_eq!(input.unicode_pad_width(6, false), 2);
+        }
+
+        #[test]
+        fn zero_width() {
+            assert_eq!("你好".unicode_pad(0, Alignment::Left, true), "");
+            assert_eq!("你好".unicode_pad(0, Alignment::Left, false), "你好");
+        }
+
+        #[test]
+        fn zero_width_not_truncated() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                assert!(matches!(
+                    "你好".unicode_pad(0, align, false),
+                    Cow::Borrowed("你好")
+                ));
+                assert!(matches!(
+                    "ab".unicode_pad(0, align, false),
+                    Cow::Borrowed("ab")
+                ));
+            }
+        }
+
+        #[test]
+        fn not_truncated_borrowed() {
+            let input = "你好吗";
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for target_width in [0, 3, 5, 6] {
+                    match input.unicode_pad(target_width, align, false) {
+                        Cow::Borrowed(padded) => {
+                            assert_eq!(padded.as_ptr(), input.as_ptr());
+                            assert_eq!(padded.len(), input.len());
+                        }
+                        Cow::Owned(padded) => panic!("{:?} was copied", padded),
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn exact_width_borrowed() {
+            let input = "你好";
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                match input.unicode_pad(4, align, true) {
+                    Cow::Borrowed(padded) => {
+                        assert_eq!(padded.as_ptr(), input.as_ptr());
+                        assert_eq!(padded.len(), input.len());
+                    }
+                    Cow::Owned(padded) => panic!("{:?} was copied", padded),
+                }
+            }
+        }
+
+        #[test]
+        fn less_than_limit() {
+            assert_eq!("你".unicode_pad(4, Alignment::Left, true), "你  ");
+            assert_eq!("你".unicode_pad(4, Alignment::Left, false), "你  ");
+        }
+
+        #[test]
+        fn width_at_boundary() {
+            assert_eq!("你好吗".unicode_pad(4, Alignment::Left, true), "你好");
+            assert_eq!("你好吗".unicode_pad(4, Alignment::Left, false), "你好吗");
+        }
+
+        #[test]
+        fn width_not_boundary() {
+            // above limit wide chars not at boundary
+            assert_eq!("你好吗".unicode_pad(3, Alignment::Left, true), "你 ");
+            assert_eq!("你好吗".unicode_pad(1, Alignment::Left, true), " ");
+            assert_eq!("你好吗".unicode_pad(3, Alignment::Left, false), "你好吗");
+
+            assert_eq!("你好吗".unicode_pad(3, Alignment::Center, true), "你 ");
+
+            assert_eq!("你好吗".unicode_pad(3, Alignment::Right, true), " 你");
+        }
+
+        #[test]
+        fn width_not_boundary_right() {
+            // the fill is added on the left, before the truncated content
+            assert_eq!("你好吗".unicode_pad(5, Alignment::Right, true), " 你好");
+            assert_eq!("你好吗".unicode_pad(1, Alignment::Right, true), " ");
+            assert_eq!("a你好".unicode_pad(4, Alignment::Right, true), " a你");
+            assert_eq!("你好吗".unicode_pad(5, Alignment::Right, false), "你好吗");
+        }
+
+        #[test]
+        fn width_not_boundary_center() {
+            // an odd fill puts the extra space on the right
+            assert_eq!("你好吗".unicode_pad(5, Alignment::Center, true), "你好 ");
+            assert_eq!("你好吗".unicode_pad(1, Alignment::Center, true), " ");
+            assert_eq!("a你好".unicode_pad(4, Alignment::Center, true), "a你 ");
+            assert_eq!("a你好吗".unicode_pad(6, Alignment::Center, true), "a你好 ");
+            assert_eq!("你好吗".unicode_pad(5, Alignment::Center, false), "你好吗");
+        }
+
+        #[test]
+        fn width_not_boundary_center_split_evenly() {
+            // the column left by the wide character joins the padding, which is split evenly
+            assert_eq!("你好吗".unicode_pad(8, Alignment::Center, true), " 你好吗 ");
+            assert_eq!(
+                "你好吗呀".unicode_pad(7, Alignment::Center, true),
+                "你好吗 "
+            );
+            assert_eq!(
+                "你好吗呀".unicode_pad(8, Alignment::Center, true),
+                "你好吗呀"
+            );
+            assert_eq!("好".unicode_pad(5, Alignment::Center, true), " 好  ");
+            assert_eq!(
+                "你好吗".unicode_pad_asymmetric(3, Alignment::Center, Alignment::Center, true),
+                "好 "
+            );
+        }
+
+        #[test]
+        fn width_not_boundary_exact_width() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for target_width in 0..8 {
+                    let padded = "你好吗".unicode_pad(target_width, align, true);
+                    assert_eq!(padded.width(), target_width);
+                }
+            }
+        }
+
+        #[test]
+        fn borrowed_when_not_padded() {
+            for input in ["你好吗", "abc\u{200B}", "ab\u{0306}c"] {
+                for target_width in 0..4 {
+                    assert!(matches!(
+                        input.unicode_pad(target_width, Alignment::Left, false),
+                        Cow::Borrowed(s) if s == input
+                    ));
+                }
+            }
+        }
+    }
+
+    mod pad_width {
+        use super::*;
+
+        #[test]
+        fn padded() {
+            assert_eq!("".unicode_pad_width(3, true), 3);
+            assert_eq!("你".unicode_pad_width(4, false), 2);
+            assert_eq!("你好吗".unicode_pad_width(3, true), 1);
+        }
+
+        #[test]
+        fn not_padded() {
+            assert_eq!("你好".unicode_pad_width(0, true), 0);
+            assert_eq!("你好".unicode_pad_width(4, true), 0);
+            assert_eq!("你好吗".unicode_pad_width(3, false), 0);
+            assert_eq!("你好吗".unicode_pad_width(4, false), 0);
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn same_as_pad() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for truncate in [false, true] {
+                    for target_width in 0..8 {
+                        let padded = input.unicode_pad(target_width, Alignment::Center, truncate);
+                        let content_width = padded.trim_matches(' ').width();
+                        assert_eq!(
+                            input.unicode_pad_width(target_width, truncate),
+                            padded.width().saturating_sub(content_width),
+                            "{input:?} at {target_width}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod fit_all_alignments {
+        use super::*;
+
+        #[test]
+        fn same_as_pad() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es", "123👨‍👩‍👧‍👦456"]
+            {
+                for width in 0..12 {
+                    let all = input.unicode_fit_all_alignments(width);
+                    for (result, align) in
+                        all.iter()
+                            .zip([Alignment::Left, Alignment::Center, Alignment::Right])
+                    {
+                        assert_eq!(*result, input.unicode_pad(width, align, true));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn exact_width_borrowed() {
+            for result in "你好吗".unicode_fit_all_alignments(4) {
+                assert!(matches!(result, Cow::Borrowed("你好")));
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad_into {
+        use super::*;
+
+        #[test]
+        fn appends() {
+            let mut buf = String::from("|");
+            "你".unicode_pad_into(4, Alignment::Center, true, '-', &mut buf);
+            buf.push('|');
+            "你好吗".unicode_pad_into(3, Alignment::Right, true, '-', &mut buf);
+            buf.push('|');
+            "你好吗".unicode_pad_into(3, Alignment::Right, false, '-', &mut buf);
+            assert_eq!(buf, "|-你-|-你|你好吗");
+        }
+
+        #[test]
+        fn wide_fill_never_overshoots() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for target_width in 0..9 {
+                    let mut buf = String::new();
+                    "a".unicode_pad_into(target_width, align, true, '＊', &mut buf);
+                    assert_eq!(buf.width(), target_width, "{:?}", buf);
+                }
+            }
+            // the odd column is topped off with a space on the outer side
+            let mut buf = String::new();
+            "a".unicode_pad_into(4, Alignment::Left, true, '＊', &mut buf);
+            "a".unicode_pad_into(4, Alignment::Right, true, '＊', &mut buf);
+            assert_eq!(buf, "a＊  ＊a");
+        }
+
+        #[test]
+        fn same_as_pad() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for truncate in [false, true] {
+                        for target_width in 0..8 {
+                            let mut buf = String::new();
+                            input.unicode_pad_into(target_width, align, truncate, ' ', &mut buf);
+                            assert_eq!(buf, input.unicode_pad(target_width, align, truncate));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_mapped {
+        use super::*;
+
+        fn fullwidth_digits(ch: char) -> Option<char> {
+            ch.to_digit(10)
+                .and_then(|digit| char::from_u32(0xFF10_u32.checked_add(digit)?))
+        }
+
+        #[test]
+        fn identity() {
+            assert!(matches!(
+                "abc123".unicode_truncate_mapped(4, |_| None),
+                (Cow::Borrowed("abc1"), 4)
+            ));
+            assert!(matches!(
+                "abc".unicode_truncate_mapped(4, fullwidth_digits),
+                (Cow::Borrowed("abc"), 3)
+            ));
+        }
+
+        #[test]
+        fn measured_after_remapping() {
+            assert_eq!(
+                "a123".unicode_truncate_mapped(4, fullwidth_digits),
+                ("a１".into(), 3)
+            );
+            assert_eq!(
+                "a123".unicode_truncate_mapped(7, fullwidth_digits),
+                ("a１２３".into(), 7)
+            );
+            assert_eq!(
+                "123".unicode_truncate_mapped(1, fullwidth_digits),
+                ("".into(), 0)
+            );
+        }
+
+        #[test]
+        fn remap_to_narrow() {
+            let halfwidth = |ch: char| match ch {
+                '你' => Some('x'),
+                _ => None,
+            };
+            assert_eq!(
+                "你你你".unicode_truncate_mapped(2, halfwidth),
+                ("xx".into(), 2)
+            );
+        }
+
+        #[test]
+        fn stops_at_width() {
+            let calls = core::cell::Cell::new(0_usize);
+            let counted = |ch: char| {
+                calls.set(calls.get().saturating_add(1));
+                fullwidth_digits(ch)
+            };
+            assert_eq!(
+                "a123456789".unicode_truncate_mapped(3, counted),
+                ("a１".into(), 3)
+            );
+            // the digit not fitting any more is the last one remapped
+            assert_eq!(calls.get(), 3);
+
+            assert!(matches!(
+                "ab1".unicode_truncate_mapped(2, fullwidth_digits),
+                (Cow::Borrowed("ab"), 2)
+            ));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad_asymmetric {
+        use super::*;
+
+        #[test]
+        fn same_alignment_as_pad() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for truncate in [false, true] {
+                    for target_width in 0..8 {
+                        // unicode_pad always truncates the end
+                        assert_eq!(
+                            "你好吗".unicode_pad_asymmetric(
+                                target_width,
+                                Alignment::Left,
+                                align,
+                                truncate
+                            ),
+                            "你好吗".unicode_pad(target_width, align, truncate)
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn truncate_start_pad_right() {
+            assert_eq!(
+                "你好吗".unicode_pad_asymmetric(5, Alignment::Right, Alignment::Left, true),
+                "好吗 "
+            );
+            assert_eq!(
+                "你好吗".unicode_pad_asymmetric(3, Alignment::Right, Alignment::Center, true),
+                "吗 "
+            );
+        }
+
+        #[test]
+        fn truncate_center_pad_left() {
+            assert_eq!(
+                "你好吗呀".unicode_pad_asymmetric(5, Alignment::Center, Alignment::Right, true),
+                " 好吗"
+            );
+        }
+
+        #[test]
+        fn no_truncation() {
+            assert!(matches!(
+                "你好吗".unicode_pad_asymmetric(3, Alignment::Right, Alignment::Left, false),
+                Cow::Borrowed("你好吗")
+            ));
+        }
+    }
+
+    #[cfg(feature = "normalization")]
+    mod pad_normalized {
+        use super::*;
+
+        #[test]
+        fn same_result_for_both_forms() {
+            for input in ["caf\u{E9}", "cafe\u{0301}"] {
+                for target_width in 0..7 {
+                    assert_eq!(
+                        input.unicode_pad_normalized(
+                            target_width,
+                            Alignment::Right,
+                            true,
+                            NormalizationForm::Nfc
+                        ),
+                        "caf\u{E9}".unicode_pad(target_width, Alignment::Right, true)
+                    );
+                    assert_eq!(
+                        input.unicode_pad_normalized(
+                            target_width,
+                            Alignment::Right,
+                            true,
+                            NormalizationForm::Nfd
+                        ),
+                        "cafe\u{0301}".unicode_pad(target_width, Alignment::Right, true)
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn borrowed_if_normalized() {
+            assert!(matches!(
+                "caf\u{E9}".unicode_pad_normalized(
+                    4,
+                    Alignment::Left,
+                    false,
+                    NormalizationForm::Nfc
+                ),
+                Cow::Borrowed("caf\u{E9}")
+            ));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_with_caret {
+        use super::*;
 
         #[test]
         fn empty() {
-            assert_eq!("".unicode_truncate(4), ("", 0));
+            assert_eq!("".unicode_truncate_with_caret(4, '█'), ("█".into(), 1));
+        }
+
+        #[test]
+        fn no_room_for_caret() {
+            assert_eq!("abc".unicode_truncate_with_caret(0, '█'), ("".into(), 0));
+            assert_eq!("abc".unicode_truncate_with_caret(1, '你'), ("".into(), 0));
         }
 
         #[test]
-        fn zero_width() {
-            assert_eq!("ab".unicode_truncate(0), ("", 0));
-            assert_eq!("你好".unicode_truncate(0), ("", 0));
+        fn less_than_limit() {
+            assert_eq!(
+                "abc".unicode_truncate_with_caret(4, '█'),
+                ("abc█".into(), 4)
+            );
+            assert_eq!("你".unicode_truncate_with_caret(4, '█'), ("你█".into(), 3));
+        }
+
+        #[test]
+        fn width_includes_caret() {
+            for input in ["", "abc", "你好吗", "a👨‍👩‍👧‍👦b"] {
+                for max_width in 0..8 {
+                    let (result, width) = input.unicode_truncate_with_caret(max_width, '█');
+                    assert_eq!(result.width(), width, "{input:?} at {max_width}");
+                }
+            }
+        }
+
+        #[test]
+        fn exactly_max_width_when_truncated() {
+            assert_eq!(
+                "abcd".unicode_truncate_with_caret(4, '█'),
+                ("abc█".into(), 4)
+            );
+            assert_eq!(
+                "你好吗".unicode_truncate_with_caret(5, '█'),
+                ("你好█".into(), 5)
+            );
+            assert_eq!(
+                "你好吗".unicode_truncate_with_caret(4, '█'),
+                ("你 █".into(), 4)
+            );
+            assert_eq!(
+                "你好吗".unicode_truncate_with_caret(2, '█'),
+                (" █".into(), 2)
+            );
+        }
+
+        #[test]
+        fn wide_caret() {
+            assert_eq!(
+                "abcd".unicode_truncate_with_caret(4, '你'),
+                ("ab你".into(), 4)
+            );
+        }
+
+        #[test]
+        fn trailing_zwj_not_joined_with_caret() {
+            // the input ends with a dangling ZWJ, which would join the emoji caret to the emoji
+            let (result, width) = "ab😀\u{200D}".unicode_truncate_with_caret(10, '👋');
+            assert_eq!((result.as_ref(), width), ("ab😀👋", 6));
+            assert_eq!(result.graphemes(true).count(), 4);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_ellipsis {
+        use super::*;
+
+        #[test]
+        fn trailing_zwj_not_joined_with_ellipsis() {
+            let input = "😀\u{200D}abc";
+            let (result, width) = input.unicode_truncate_ellipsis(4, Alignment::Left, "👋");
+            assert_eq!((result.as_ref(), width), ("😀👋", 4));
+            assert_eq!(result.graphemes(true).count(), 2);
+            // the head is not joined with the tail either
+            let input = "😀\u{200D}ab😀";
+            let (result, width) = input.unicode_truncate_ellipsis(4, Alignment::Center, "");
+            assert_eq!((result.as_ref(), width), ("😀😀", 4));
         }
 
         #[test]
-        fn less_than_limit() {
-            assert_eq!("abc".unicode_truncate(4), ("abc", 3));
-            assert_eq!("你".unicode_truncate(4), ("你", 2));
+        fn empty() {
+            assert!(matches!(
+                "".unicode_truncate_ellipsis(4, Alignment::Left, "…"),
+                (Cow::Borrowed(""), 0)
+            ));
         }
 
         #[test]
-        fn at_boundary() {
-            assert_eq!("boundary".unicode_truncate(5), ("bound", 5));
-            assert_eq!("你好吗".unicode_truncate(4), ("你好", 4));
+        fn fits() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                assert!(matches!(
+                    "你好".unicode_truncate_ellipsis(4, align, "…"),
+                    (Cow::Borrowed("你好"), 4)
+                ));
+            }
         }
 
         #[test]
-        fn not_boundary() {
-            assert_eq!("你好吗".unicode_truncate(3), ("你", 2));
-            assert_eq!("你好吗".unicode_truncate(1), ("", 0));
+        fn ellipsis_on_removed_side() {
+            let input = "abcdefgh";
+            assert_eq!(
+                input.unicode_truncate_ellipsis(5, Alignment::Left, "…"),
+                ("abcd…".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_ellipsis(5, Alignment::Right, "…"),
+                ("…efgh".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_ellipsis(5, Alignment::Center, "…"),
+                ("ab…gh".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_ellipsis(6, Alignment::Center, "…"),
+                ("abc…gh".into(), 6)
+            );
         }
 
         #[test]
-        fn zero_width_char_in_middle() {
-            // zero width character in the middle is intact
-            assert_eq!("y\u{0306}es".unicode_truncate(2), ("y\u{0306}e", 2));
+        fn wide_chars() {
+            let input = "你好吗你好吗";
+            assert_eq!(
+                input.unicode_truncate_ellipsis(6, Alignment::Left, "..."),
+                ("你...".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_ellipsis(6, Alignment::Right, "..."),
+                ("...吗".into(), 5)
+            );
+            assert_eq!(
+                input.unicode_truncate_ellipsis(6, Alignment::Center, "…"),
+                ("你…吗".into(), 5)
+            );
+            // the column left by the head is given to the tail
+            assert_eq!(
+                input.unicode_truncate_ellipsis(7, Alignment::Center, "…"),
+                ("你…好吗".into(), 7)
+            );
         }
 
         #[test]
-        fn keep_zero_width_char_at_boundary() {
-            // zero width character at end is preserved
+        fn ellipsis_too_wide() {
             assert_eq!(
-                "y\u{0306}ey\u{0306}s".unicode_truncate(3),
-                ("y\u{0306}ey\u{0306}", 3)
+                "abcdef".unicode_truncate_ellipsis(2, Alignment::Left, "..."),
+                ("..".into(), 2)
+            );
+            assert_eq!(
+                "abcdef".unicode_truncate_ellipsis(0, Alignment::Center, "..."),
+                ("".into(), 0)
             );
         }
 
         #[test]
-        fn family_stays_together() {
-            let input = "123👨‍👩‍👧‍👦456";
-
-            // Family emoji should be of width 2
-            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
-
-            assert_eq!(input.unicode_truncate(4), ("123", 3));
-            assert_eq!(input.unicode_truncate(5), ("123👨‍👩‍👧‍👦", 5));
-            assert_eq!(input.unicode_truncate(6), ("123👨‍👩‍👧‍👦4", 6));
-            assert_eq!(input.unicode_truncate(20), (input, 8));
+        fn width_includes_ellipsis() {
+            for input in [
+                "",
+                "abc",
+                "你好",
+                "abcdefgh",
+                "你好吗你好吗",
+                "a👨‍👩‍👧‍👦b\u{0306}c",
+            ] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for max_width in 0..12 {
+                        for ellipsis in ["…", "...", "你"] {
+                            let (result, width) =
+                                input.unicode_truncate_ellipsis(max_width, align, ellipsis);
+                            assert_eq!(result.width(), width, "{input:?} at {max_width}");
+                            assert!(width <= max_width);
+                        }
+                    }
+                }
+            }
         }
     }
 
-    mod truncate_start {
+    #[cfg(feature = "std")]
+    mod truncate_ellipsis_min_content {
         use super::*;
 
         #[test]
-        fn empty() {
-            assert_eq!("".unicode_truncate_start(4), ("", 0));
+        fn same_as_ellipsis_when_enough_content() {
+            let input = "你好吗你好吗";
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for max_width in 0..14 {
+                    assert_eq!(
+                        input.unicode_truncate_ellipsis_min_content(max_width, align, "…", 0),
+                        input.unicode_truncate_ellipsis(max_width, align, "…")
+                    );
+                }
+                for max_width in 3..14 {
+                    assert_eq!(
+                        input.unicode_truncate_ellipsis_min_content(max_width, align, "…", 1),
+                        input.unicode_truncate_ellipsis(max_width, align, "…")
+                    );
+                }
+            }
         }
 
         #[test]
-        fn zero_width() {
-            assert_eq!("ab".unicode_truncate_start(0), ("", 0));
-            assert_eq!("你好".unicode_truncate_start(0), ("", 0));
+        fn exceeds_max_width() {
+            let input = "abcdef";
+            let truncate = |max_width, align| {
+                input.unicode_truncate_ellipsis_min_content(max_width, align, "...", 2)
+            };
+            assert_eq!(truncate(4, Alignment::Left), ("ab...".into(), 5));
+            assert_eq!(truncate(0, Alignment::Right), ("...ef".into(), 5));
+            assert_eq!(truncate(3, Alignment::Center), ("a...f".into(), 5));
         }
 
         #[test]
-        fn less_than_limit() {
-            assert_eq!("abc".unicode_truncate_start(4), ("abc", 3));
-            assert_eq!("你".unicode_truncate_start(4), ("你", 2));
+        fn odd_count_centered() {
+            assert_eq!(
+                "abcdef".unicode_truncate_ellipsis_min_content(1, Alignment::Center, "…", 3),
+                ("a…ef".into(), 4)
+            );
         }
 
         #[test]
-        fn at_boundary() {
-            assert_eq!("boundary".unicode_truncate_start(5), ("ndary", 5));
-            assert_eq!("你好吗".unicode_truncate_start(4), ("好吗", 4));
+        fn graphemes_not_columns() {
+            assert_eq!(
+                "y\u{0306}es".unicode_truncate_ellipsis_min_content(1, Alignment::Left, "…", 1),
+                ("y\u{0306}…".into(), 2)
+            );
         }
 
         #[test]
-        fn not_boundary() {
-            assert_eq!("你好吗".unicode_truncate_start(3), ("吗", 2));
-            assert_eq!("你好吗".unicode_truncate_start(1), ("", 0));
+        fn whole_string_when_short() {
+            assert_eq!(
+                "你好".unicode_truncate_ellipsis_min_content(1, Alignment::Left, "…", 2),
+                ("你好".into(), 4)
+            );
+            assert!(matches!(
+                "你好"
+                    .unicode_truncate_ellipsis_min_content(1, Alignment::Center, "…", 5)
+                    .0,
+                Cow::Borrowed("你好")
+            ));
         }
+    }
+
+    #[cfg(feature = "std")]
+    mod truncate_after_prefix {
+        use super::*;
 
         #[test]
-        fn zero_width_char_in_middle() {
-            // zero width character in middle is preserved
+        fn value_truncated() {
+            let line = "ERROR: 磁盘已满";
             assert_eq!(
-                "y\u{0306}ey\u{0306}s".unicode_truncate_start(2),
-                ("y\u{0306}s", 2)
+                line.unicode_truncate_after_prefix(7, 12, "…"),
+                ("ERROR: 磁盘…".into(), 12)
+            );
+            assert_eq!(
+                line.unicode_truncate_after_prefix(7, 11, "…"),
+                ("ERROR: 磁…".into(), 10)
+            );
+            // only the ellipsis fits after the prefix
+            assert_eq!(
+                line.unicode_truncate_after_prefix(7, 8, "…"),
+                ("ERROR: …".into(), 8)
             );
         }
 
         #[test]
-        fn remove_zero_width_char_at_boundary() {
-            // zero width character in the middle at the cutting boundary is removed
-            assert_eq!("y\u{0306}es".unicode_truncate_start(2), ("es", 2));
+        fn fits_borrowed() {
+            let line = "ERROR: 磁盘已满";
+            for max_width in [15, 20] {
+                assert!(matches!(
+                    line.unicode_truncate_after_prefix(7, max_width, "…"),
+                    (Cow::Borrowed(s), 15) if s == line
+                ));
+            }
         }
 
         #[test]
-        fn family_stays_together() {
-            let input = "123👨‍👩‍👧‍👦456";
-
-            // Family emoji should be of width 2
-            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
+        fn wide_char_at_prefix_end() {
+            // the prefix stops before the wide character it would split
+            assert_eq!(
+                "你好吗你好吗".unicode_truncate_after_prefix(3, 6, "…"),
+                ("你好…".into(), 5)
+            );
+        }
 
-            assert_eq!(input.unicode_truncate_start(4), ("456", 3));
-            assert_eq!(input.unicode_truncate_start(5), ("👨‍👩‍👧‍👦456", 5));
-            assert_eq!(input.unicode_truncate_start(6), ("3👨‍👩‍👧‍👦456", 6));
-            assert_eq!(input.unicode_truncate_start(20), (input, 8));
+        #[test]
+        fn prefix_not_narrower_than_max_width() {
+            let line = "ERROR: disk full";
+            assert_eq!(
+                line.unicode_truncate_after_prefix(7, 7, "…"),
+                ("ERROR:…".into(), 7)
+            );
+            assert_eq!(
+                line.unicode_truncate_after_prefix(10, 3, "..."),
+                ("...".into(), 3)
+            );
         }
     }
 
-    mod truncate_centered {
+    #[cfg(feature = "std")]
+    mod fit {
         use super::*;
 
         #[test]
-        fn empty() {
-            assert_eq!("".unicode_truncate_centered(4), ("", 0));
+        fn exact_width() {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for width in 0..10 {
+                    assert_eq!("你好吗你好吗".unicode_fit(width, align, "…").width(), width);
+                    assert_eq!("你好".unicode_fit(width, align, "…").width(), width);
+                }
+            }
         }
 
         #[test]
-        fn zero_width() {
-            assert_eq!("ab".unicode_truncate_centered(0), ("", 0));
-            assert_eq!("你好".unicode_truncate_centered(0), ("", 0));
+        fn padding_follows_alignment() {
+            assert_eq!("你好".unicode_fit(6, Alignment::Left, "…"), "你好  ");
+            assert_eq!("你好".unicode_fit(6, Alignment::Center, "…"), " 你好 ");
+            assert_eq!("你好".unicode_fit(6, Alignment::Right, "…"), "  你好");
+            assert_eq!(
+                "你好吗你好吗".unicode_fit(6, Alignment::Right, "…"),
+                " …好吗"
+            );
         }
 
         #[test]
-        fn less_than_limit() {
-            assert_eq!("abc".unicode_truncate_centered(4), ("abc", 3));
-            assert_eq!("你".unicode_truncate_centered(4), ("你", 2));
+        fn borrowed_when_exact() {
+            assert!(matches!(
+                "你好".unicode_fit(4, Alignment::Left, "…"),
+                Cow::Borrowed("你好")
+            ));
         }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad_opt_fill {
+        use super::*;
 
-        /// The source code has special handling for small `min_removal_width` (half-point)
         #[test]
-        fn truncate_exactly_one() {
-            assert_eq!("abcd".unicode_truncate_centered(3), ("abc", 3));
+        fn no_fill_same_as_truncate_aligned() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for target_width in 0..8 {
+                        let result = input.unicode_pad_opt_fill(target_width, align, None);
+                        let (truncated, _) = input.unicode_truncate_aligned(target_width, align);
+                        assert!(matches!(result, Cow::Borrowed(s) if s == truncated));
+                    }
+                }
+            }
         }
 
         #[test]
-        fn at_boundary() {
-            assert_eq!(
-                "boundaryboundary".unicode_truncate_centered(5),
-                ("arybo", 5)
-            );
+        fn fill_pads_to_width() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for target_width in 0..8 {
+                        let result = input.unicode_pad_opt_fill(target_width, align, Some('＊'));
+                        assert_eq!(result.width(), target_width, "{:?}", result);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn exact_width_borrowed() {
+            assert!(matches!(
+                "你好".unicode_pad_opt_fill(4, Alignment::Center, Some('.')),
+                Cow::Borrowed("你好")
+            ));
             assert_eq!(
-                "你好吗你好吗你好吗".unicode_truncate_centered(4),
-                ("你好", 4)
+                "你好".unicode_pad_opt_fill(5, Alignment::Center, Some('.')),
+                "你好."
             );
         }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad_strategy {
+        use super::*;
 
         #[test]
-        fn not_boundary() {
-            assert_eq!("你好吗你好吗".unicode_truncate_centered(3), ("吗", 2));
-            assert_eq!("你好吗你好吗".unicode_truncate_centered(1), ("", 0));
+        fn space_fill_same_as_pad() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for target_width in 0..8 {
+                        for truncate in [false, true] {
+                            assert_eq!(
+                                input.unicode_pad_strategy(
+                                    target_width,
+                                    align,
+                                    truncate,
+                                    &SpaceFill
+                                ),
+                                input.unicode_pad(target_width, align, truncate)
+                            );
+                        }
+                    }
+                }
+            }
         }
 
         #[test]
-        fn zero_width_char_in_middle() {
-            // zero width character in middle is preserved
-            assert_eq!(
-                "yy\u{0306}es".unicode_truncate_centered(2),
-                ("y\u{0306}e", 2)
-            );
+        fn exact_width() {
+            let strategies: [&dyn FillStrategy; 3] =
+                [&CharFill('＊'), &CharFill('.'), &PatternFill("-你=")];
+            for fill in strategies.iter().copied() {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for target_width in 0..8 {
+                        let result = "你好a".unicode_pad_strategy(target_width, align, true, fill);
+                        assert_eq!(result.width(), target_width, "{:?}", result);
+                    }
+                }
+            }
         }
 
         #[test]
-        fn zero_width_char_at_boundary() {
-            // zero width character at the cutting boundary in the start is removed
-            // but those in the end is kept.
-            assert_eq!(
-                "y\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}"
-                    .unicode_truncate_centered(2),
-                ("b\u{0306}y\u{0306}", 2)
-            );
-            assert_eq!(
-                "ay\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}"
-                    .unicode_truncate_centered(2),
-                ("a\u{0306}b\u{0306}", 2)
-            );
+        fn pattern_continues_after_content() {
             assert_eq!(
-                "y\u{0306}ea\u{0306}b\u{0306}y\u{0306}ea\u{0306}b\u{0306}a"
-                    .unicode_truncate_centered(2),
-                ("b\u{0306}y\u{0306}", 2)
+                "ab".unicode_pad_strategy(8, Alignment::Center, true, &PatternFill("123")),
+                "123ab312"
             );
         }
+    }
+
+    #[cfg(feature = "std")]
+    mod pad_right_gutter {
+        use super::*;
 
         #[test]
-        fn control_char() {
-            use unicode_width::UnicodeWidthChar;
-            assert_eq!("\u{0019}".width(), 1);
-            assert_eq!('\u{0019}'.width(), None);
-            assert_eq!("\u{0019}".unicode_truncate(2), ("\u{0019}", 1));
+        fn no_gutter_same_as_pad() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for target_width in 0..8 {
+                    for truncate in [false, true] {
+                        assert_eq!(
+                            input.unicode_pad_right_gutter(target_width, 0, truncate),
+                            input.unicode_pad(target_width, Alignment::Right, truncate)
+                        );
+                    }
+                }
+            }
         }
 
         #[test]
-        fn family_stays_together() {
-            let input = "123👨‍👩‍👧‍👦456";
+        fn exactly_fills() {
+            assert_eq!("123".unicode_pad_right_gutter(5, 2, true), "123  ");
+            assert_eq!("你好".unicode_pad_right_gutter(5, 1, false), "你好 ");
+        }
 
-            // Family emoji should be of width 2
-            assert_eq!("👨‍👩‍👧‍👦".width(), 2);
+        #[test]
+        fn underflows() {
+            assert_eq!("1".unicode_pad_right_gutter(5, 2, true), "  1  ");
+            assert_eq!("".unicode_pad_right_gutter(3, 1, true), "   ");
+        }
 
-            assert_eq!(input.unicode_truncate_centered(1), ("", 0));
-            assert_eq!(input.unicode_truncate_centered(2), ("👨‍👩‍👧‍👦", 2));
-            assert_eq!(input.unicode_truncate_centered(4), ("3👨‍👩‍👧‍👦4", 4));
-            assert_eq!(input.unicode_truncate_centered(6), ("23👨‍👩‍👧‍👦45", 6));
-            assert_eq!(input.unicode_truncate_centered(20), (input, 8));
+        #[test]
+        fn overflows() {
+            assert_eq!("12345".unicode_pad_right_gutter(5, 2, true), "123  ");
+            // a wide character at the truncation point leaves a column for the padding
+            assert_eq!("你好吗".unicode_pad_right_gutter(6, 1, true), " 你好 ");
+            assert!(matches!(
+                "12345".unicode_pad_right_gutter(5, 2, false),
+                Cow::Borrowed("12345")
+            ));
         }
-    }
 
-    #[test]
-    fn truncate_aligned() {
-        assert_eq!("abc".unicode_truncate_aligned(1, Alignment::Left), ("a", 1));
-        assert_eq!(
-            "abc".unicode_truncate_aligned(1, Alignment::Center),
-            ("b", 1)
-        );
-        assert_eq!(
-            "abc".unicode_truncate_aligned(1, Alignment::Right),
-            ("c", 1)
-        );
+        #[test]
+        fn gutter_too_wide() {
+            assert_eq!("12".unicode_pad_right_gutter(3, 5, true), "   ");
+            assert_eq!("12".unicode_pad_right_gutter(0, 5, true), "");
+        }
     }
 
     #[cfg(feature = "std")]
-    mod pad {
+    mod pad_asymmetric_fill {
         use super::*;
 
         #[test]
-        fn zero_width() {
-            assert_eq!("你好".unicode_pad(0, Alignment::Left, true), "");
-            assert_eq!("你好".unicode_pad(0, Alignment::Left, false), "你好");
+        fn same_as_pad_with_spaces() {
+            for input in ["", "abc", "你好吗", "y\u{0306}es"] {
+                for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                    for target_width in 0..8 {
+                        for truncate in [false, true] {
+                            assert_eq!(
+                                input.unicode_pad_asymmetric_fill(
+                                    target_width,
+                                    align,
+                                    truncate,
+                                    ' ',
+                                    ' '
+                                ),
+                                input.unicode_pad(target_width, align, truncate)
+                            );
+                        }
+                    }
+                }
+            }
         }
 
         #[test]
-        fn less_than_limit() {
-            assert_eq!("你".unicode_pad(4, Alignment::Left, true), "你  ");
-            assert_eq!("你".unicode_pad(4, Alignment::Left, false), "你  ");
+        fn fill_per_side() {
+            let pad = |align| "ab".unicode_pad_asymmetric_fill(7, align, true, '[', ']');
+            assert_eq!(pad(Alignment::Left), "ab]]]]]");
+            assert_eq!(pad(Alignment::Center), "[[ab]]]");
+            assert_eq!(pad(Alignment::Right), "[[[[[ab");
         }
 
         #[test]
-        fn width_at_boundary() {
-            assert_eq!("你好吗".unicode_pad(4, Alignment::Left, true), "你好");
-            assert_eq!("你好吗".unicode_pad(4, Alignment::Left, false), "你好吗");
+        fn not_truncated_borrowed() {
+            assert!(matches!(
+                "你好吗".unicode_pad_asymmetric_fill(5, Alignment::Center, false, '[', ']'),
+                Cow::Borrowed("你好吗")
+            ));
         }
 
         #[test]
-        fn width_not_boundary() {
-            // above limit wide chars not at boundary
-            assert_eq!("你好吗".unicode_pad(3, Alignment::Left, true), "你 ");
-            assert_eq!("你好吗".unicode_pad(1, Alignment::Left, true), " ");
-            assert_eq!("你好吗".unicode_pad(3, Alignment::Left, false), "你好吗");
+        #[should_panic(expected = "right_fill should be 1 column wide")]
+        fn wide_fill() {
+            let _ = "ab".unicode_pad_asymmetric_fill(4, Alignment::Left, true, '[', '＊');
+        }
+    }
 
-            assert_eq!("你好吗".unicode_pad(3, Alignment::Center, true), "你 ");
+    #[cfg(feature = "std")]
+    mod pad_sep {
+        use super::*;
 
-            assert_eq!("你好吗".unicode_pad(3, Alignment::Right, true), " 你");
+        #[test]
+        fn no_padding() {
+            assert!(matches!(
+                "你好".unicode_pad_sep(4, Alignment::Left, '.', '|'),
+                Cow::Borrowed("你好")
+            ));
+            assert_eq!(
+                "你好吗".unicode_pad_sep(4, Alignment::Left, '.', '|'),
+                "你好"
+            );
+        }
+
+        #[test]
+        fn sep_on_padding_side() {
+            assert_eq!("你".unicode_pad_sep(5, Alignment::Left, '.', '|'), "你|..");
+            assert_eq!("你".unicode_pad_sep(5, Alignment::Right, '.', '|'), "..|你");
+            assert_eq!(
+                "你".unicode_pad_sep(6, Alignment::Center, '.', '|'),
+                ".|你|."
+            );
+        }
+
+        #[test]
+        fn single_column_of_padding() {
+            assert_eq!("你".unicode_pad_sep(3, Alignment::Left, '.', '|'), "你|");
+            assert_eq!("你".unicode_pad_sep(3, Alignment::Right, '.', '|'), "|你");
+            assert_eq!("你".unicode_pad_sep(3, Alignment::Center, '.', '|'), "你|");
+            assert_eq!(
+                "你好吗".unicode_pad_sep(3, Alignment::Right, '.', '\u{2009}'),
+                "\u{2009}你"
+            );
+        }
+
+        #[test]
+        fn wide_fill_and_sep() {
+            assert_eq!(
+                "你".unicode_pad_sep(6, Alignment::Left, '＊', '|'),
+                "你|＊ "
+            );
+            assert_eq!(
+                "你".unicode_pad_sep(7, Alignment::Right, '＊', '|'),
+                "＊＊|你"
+            );
+            // a separator wider than the padding is left out
+            assert_eq!("你".unicode_pad_sep(3, Alignment::Left, '.', '＃'), "你.");
+            assert_eq!(
+                "你".unicode_pad_sep(6, Alignment::Center, '.', '＃'),
+                "＃你＃"
+            );
         }
     }
 }
@@ -0,0 +1,225 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation of lists with a count of the hidden items.
+
+use crate::{graphemes_width, UnicodeTruncateStr};
+
+/// Joins as many `items` as fit in `max_width` in terms of display width, followed by a suffix
+/// counting the hidden items. Only available when the `std` feature of this library is activated,
+/// and it is activated by default.
+///
+/// The items are joined with `separator`, and if any are hidden, the separator and
+/// `more(hidden_count)` are appended, e.g. `"rust, cli, +3 more"`. The longest prefix of the items
+/// is shown for which the suffix still fits, so the width of the suffix may depend on the count.
+/// If all items fit, no suffix is added.
+///
+/// Items are only truncated as a last resort: if not even the first item fits together with the
+/// suffix, the first item is truncated like
+/// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) to the columns the suffix
+/// leaves. If that leaves no columns for it, only the suffix for all items is returned, truncated
+/// to `max_width` if needed.
+///
+/// Returns the rendered list and the number of items shown, including a truncated first item.
+///
+/// # Arguments
+/// * `items` - the items of the list
+/// * `separator` - the separator between the items and before the suffix
+/// * `max_width` - the maximum display width
+/// * `more` - renders the suffix for the given number of hidden items
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::truncate_list;
+///
+/// let tags = ["rust", "cli", "unicode", "text", "width", "wrap"];
+/// let more = |hidden| format!("+{} more", hidden);
+/// assert_eq!(
+///     truncate_list(&tags, ", ", 30, more),
+///     ("rust, cli, unicode, +3 more".to_string(), 3)
+/// );
+/// assert_eq!(truncate_list(&tags[..2], ", ", 9, more), ("rust, cli".to_string(), 2));
+/// ```
+pub fn truncate_list<'a>(
+    items: &'a [&'a str],
+    separator: &str,
+    max_width: usize,
+    more: impl Fn(usize) -> String,
+) -> (String, usize) {
+    let separator_width = graphemes_width(separator);
+    let suffix_width = |hidden: usize| {
+        if hidden == 0 {
+            0
+        } else {
+            separator_width.saturating_add(graphemes_width(&more(hidden)))
+        }
+    };
+
+    // find the most items that fit along with the suffix
+    let mut shown = 0;
+    let mut items_width = 0usize;
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            items_width = items_width.saturating_add(separator_width);
+        }
+        items_width = items_width.saturating_add(graphemes_width(item));
+        if items_width > max_width {
+            break;
+        }
+        // unwrap is safe as index is less than the number of items
+        let count = index.checked_add(1).unwrap();
+        let hidden = items.len().saturating_sub(count);
+        if items_width.saturating_add(suffix_width(hidden)) <= max_width {
+            shown = count;
+        }
+    }
+    if shown > 0 {
+        return (
+            render(&items[..shown], None, separator, items.len(), &more),
+            shown,
+        );
+    }
+
+    if let Some(first) = items.first() {
+        let hidden = items.len().saturating_sub(1);
+        let budget = max_width.saturating_sub(suffix_width(hidden));
+        let (truncated, _) = first.unicode_truncate(budget);
+        if !truncated.is_empty() && suffix_width(hidden) <= max_width {
+            return (
+                render(&[], Some(truncated), separator, items.len(), &more),
+                1,
+            );
+        }
+        let suffix = more(items.len());
+        return (suffix.unicode_truncate(max_width).0.to_string(), 0);
+    }
+    (String::new(), 0)
+}
+
+/// Joins `shown` items, or the single `truncated` item, with `separator`, followed by the suffix
+/// for the items of `total` not shown.
+fn render(
+    shown: &[&str],
+    truncated: Option<&str>,
+    separator: &str,
+    total: usize,
+    more: impl Fn(usize) -> String,
+) -> String {
+    let mut result = String::new();
+    for item in shown.iter().copied().chain(truncated) {
+        if !result.is_empty() {
+            result.push_str(separator);
+        }
+        result.push_str(item);
+    }
+    let count = shown.len().saturating_add(usize::from(truncated.is_some()));
+    let hidden = total.saturating_sub(count);
+    if hidden > 0 {
+        result.push_str(separator);
+        result.push_str(&more(hidden));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn more(hidden: usize) -> String {
+        format!("+{} more", hidden)
+    }
+
+    #[test]
+    fn all_fit_exactly() {
+        let tags = ["rust", "cli", "unicode"];
+        // "rust, cli, unicode" is 18 columns wide, as is "rust, cli, +1 more"
+        assert_eq!(
+            truncate_list(&tags, ", ", 18, more),
+            ("rust, cli, unicode".to_string(), 3)
+        );
+        assert_eq!(
+            truncate_list(&tags, ", ", 17, more),
+            ("rust, +2 more".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn suffix_fits_exactly() {
+        let tags = ["rust", "cli", "unicode", "text"];
+        // "rust, cli, +2 more" is 18 columns wide
+        assert_eq!(
+            truncate_list(&tags, ", ", 18, more),
+            ("rust, cli, +2 more".to_string(), 2)
+        );
+        assert_eq!(
+            truncate_list(&tags, ", ", 17, more),
+            ("rust, +3 more".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn cjk_tags() {
+        let tags = ["你好", "世界", "中文", "字"];
+        assert_eq!(
+            truncate_list(&tags, "、", 20, more),
+            ("你好、世界、中文、字".to_string(), 4)
+        );
+        assert_eq!(
+            truncate_list(&tags, "、", 19, more),
+            ("你好、世界、+2 more".to_string(), 2)
+        );
+        assert_eq!(
+            truncate_list(&tags, "、", 14, more),
+            ("你好、+3 more".to_string(), 1)
+        );
+        assert_eq!(
+            truncate_list(&tags, "、", 14, more)
+                .0
+                .as_str()
+                .unicode_truncate(14)
+                .1,
+            13
+        );
+    }
+
+    #[test]
+    fn suffix_width_depends_on_count() {
+        let items: Vec<String> = (0..12).map(|i| i.to_string()).collect();
+        let items: Vec<&str> = items.iter().map(String::as_str).collect();
+        let (list, shown) = truncate_list(&items, ",", 12, more);
+        assert_eq!((list.as_str(), shown), ("0,1,+10 more", 2));
+        // the suffix for 11 hidden items is as wide, but showing one more item does not fit
+        let (list, shown) = truncate_list(&items, ",", 11, more);
+        assert_eq!((list.as_str(), shown), ("0,+11 more", 1));
+    }
+
+    #[test]
+    fn first_item_truncated() {
+        let tags = ["unicode-truncate", "cli"];
+        assert_eq!(
+            truncate_list(&tags, ", ", 12, more),
+            ("uni, +1 more".to_string(), 1)
+        );
+        assert_eq!(
+            truncate_list(&tags[..1], ", ", 7, more),
+            ("unicode".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn only_suffix() {
+        let tags = ["unicode", "cli"];
+        assert_eq!(
+            truncate_list(&tags, ", ", 8, more),
+            ("+2 more".to_string(), 0)
+        );
+        assert_eq!(truncate_list(&tags, ", ", 3, more), ("+2 ".to_string(), 0));
+        assert_eq!(truncate_list(&[], ", ", 3, more), (String::new(), 0));
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pluggable width models for matching specific terminals.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::ZwjWidth;
+
+/// A way of measuring the display width of grapheme clusters.
+///
+/// Terminals disagree on the width of some characters, as they use different `wcwidth`
+/// implementations and Unicode versions. Implementing this trait allows truncating with
+/// [`unicode_truncate_with_model`](crate::UnicodeTruncateStr::unicode_truncate_with_model) to match
+/// a specific terminal exactly.
+pub trait WidthModel {
+    /// Returns the display width of a grapheme cluster.
+    fn grapheme_width(&self, grapheme: &str) -> usize;
+}
+
+/// Measures graphemes with [`unicode_width`], like all the other methods of this library.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct UnicodeWidthModel;
+
+impl WidthModel for UnicodeWidthModel {
+    #[inline]
+    fn grapheme_width(&self, grapheme: &str) -> usize {
+        grapheme.width()
+    }
+}
+
+/// Measures graphemes like `wcwidth` of musl libc, i.e. as the sum of the widths of the individual
+/// code points.
+///
+/// Unlike [`UnicodeWidthModel`], code points are measured without context, so an emoji
+/// presentation selector does not widen the preceding character, and every part of an emoji ZWJ
+/// sequence is counted. In Latin-1, every printable character including the soft hyphen is 1
+/// column wide. Control characters, for which `wcwidth` returns -1, are counted as 0 columns.
+///
+/// Outside Latin-1, the tables of [`unicode_width`] are used for the zero-width and wide
+/// characters, so code points whose properties changed between the Unicode versions of musl and
+/// [`unicode_width`] may still differ.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::{MuslWcwidthModel, WidthModel};
+///
+/// assert_eq!(MuslWcwidthModel.grapheme_width("\u{2764}\u{FE0F}"), 1);
+/// assert_eq!(MuslWcwidthModel.grapheme_width("\u{AD}"), 1);
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct MuslWcwidthModel;
+
+impl MuslWcwidthModel {
+    fn char_width(ch: char) -> usize {
+        if u32::from(ch) < 0xFF {
+            // NUL is 0, other controls are -1
+            return if ch.is_control() { 0 } else { 1 };
+        }
+        ch.width().unwrap_or(0)
+    }
+}
+
+impl WidthModel for MuslWcwidthModel {
+    fn grapheme_width(&self, grapheme: &str) -> usize {
+        grapheme
+            .chars()
+            .map(Self::char_width)
+            .fold(0, usize::saturating_add)
+    }
+}
+
+/// Measures graphemes coarsely without the tables of [`unicode_width`]. Only available when the
+/// `compact` feature of this library is activated.
+///
+/// Graphemes starting with an ASCII character are 1 column wide, graphemes consisting of combining
+/// marks only are 0 columns wide, and all other graphemes are 2 columns wide. This is exact for
+/// ASCII and CJK text. Other characters are usually overestimated, e.g. narrow characters like `é`
+/// or `→` and zero-width characters like U+200B, so truncation may leave columns unused.
+///
+/// It is meant for size-constrained targets, e.g. WASM, where binaries only measuring with this
+/// model can leave out the width tables.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::{CompactWidthModel, WidthModel};
+///
+/// assert_eq!(CompactWidthModel.grapheme_width("a"), 1);
+/// assert_eq!(CompactWidthModel.grapheme_width("你"), 2);
+/// assert_eq!(CompactWidthModel.grapheme_width("e\u{0301}"), 1);
+/// assert_eq!(CompactWidthModel.grapheme_width("é"), 2);
+/// ```
+#[cfg(feature = "compact")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct CompactWidthModel;
+
+#[cfg(feature = "compact")]
+impl WidthModel for CompactWidthModel {
+    #[inline]
+    fn grapheme_width(&self, grapheme: &str) -> usize {
+        match grapheme.chars().next() {
+            None => 0,
+            Some(first) if first.is_ascii() => 1,
+            Some(_) if crate::is_orphaned_mark(grapheme) => 0,
+            Some(_) => 2,
+        }
+    }
+}
+
+impl WidthModel for ZwjWidth {
+    #[inline]
+    fn grapheme_width(&self, grapheme: &str) -> usize {
+        ZwjWidth::grapheme_width(*self, grapheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unicode_width_model() {
+        for grapheme in ["a", "你", "y\u{0306}", "👨‍👩‍👧‍👦", "\u{2764}\u{FE0F}", "\u{AD}"]
+        {
+            assert_eq!(UnicodeWidthModel.grapheme_width(grapheme), grapheme.width());
+        }
+    }
+
+    #[test]
+    fn musl_latin1() {
+        assert_eq!(MuslWcwidthModel.grapheme_width("a"), 1);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\u{AD}"), 1);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\u{A0}"), 1);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\0"), 0);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\u{1B}"), 0);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\u{9F}"), 0);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\r\n"), 0);
+    }
+
+    #[test]
+    fn musl_per_code_point() {
+        assert_eq!(MuslWcwidthModel.grapheme_width("你"), 2);
+        assert_eq!(MuslWcwidthModel.grapheme_width("y\u{0306}"), 1);
+        assert_eq!(MuslWcwidthModel.grapheme_width("\u{2764}\u{FE0F}"), 1);
+        assert_eq!(MuslWcwidthModel.grapheme_width("👨‍👩‍👧‍👦"), 8);
+        assert_eq!(MuslWcwidthModel.grapheme_width("🇺🇸"), 2);
+    }
+
+    #[cfg(feature = "compact")]
+    #[test]
+    fn compact() {
+        assert_eq!(CompactWidthModel.grapheme_width("a"), 1);
+        assert_eq!(CompactWidthModel.grapheme_width("a\u{0301}"), 1);
+        assert_eq!(CompactWidthModel.grapheme_width("\u{1B}"), 1);
+        assert_eq!(CompactWidthModel.grapheme_width("\r\n"), 1);
+        assert_eq!(CompactWidthModel.grapheme_width("\u{0301}"), 0);
+        assert_eq!(CompactWidthModel.grapheme_width("你"), 2);
+        assert_eq!(CompactWidthModel.grapheme_width("👨‍👩‍👧‍👦"), 2);
+        assert_eq!(CompactWidthModel.grapheme_width("é"), 2);
+    }
+
+    #[test]
+    fn zwj_width() {
+        let model: &dyn WidthModel = &ZwjWidth::Sum;
+        assert_eq!(model.grapheme_width("👨‍👩‍👧‍👦"), 8);
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Unicode normalization before measuring.
+
+use std::borrow::Cow;
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization forms, see [UAX #15](https://www.unicode.org/reports/tr15/).
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum NormalizationForm {
+    /// Canonical composition, e.g. `"e\u{0301}"` becomes `"é"`
+    Nfc,
+    /// Canonical decomposition, e.g. `"é"` becomes `"e\u{0301}"`
+    Nfd,
+}
+
+impl NormalizationForm {
+    /// Converts `s` to this form, borrowing it if it is already normalized.
+    pub(crate) fn normalize(self, s: &str) -> Cow<'_, str> {
+        match self {
+            NormalizationForm::Nfc if unicode_normalization::is_nfc(s) => Cow::Borrowed(s),
+            NormalizationForm::Nfc => Cow::Owned(s.nfc().collect()),
+            NormalizationForm::Nfd if unicode_normalization::is_nfd(s) => Cow::Borrowed(s),
+            NormalizationForm::Nfd => Cow::Owned(s.nfd().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_if_normalized() {
+        assert!(matches!(
+            NormalizationForm::Nfc.normalize("caf\u{E9}"),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            NormalizationForm::Nfd.normalize("cafe\u{0301}"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn converted() {
+        assert_eq!(
+            NormalizationForm::Nfc.normalize("cafe\u{0301}"),
+            "caf\u{E9}"
+        );
+        assert_eq!(
+            NormalizationForm::Nfd.normalize("caf\u{E9}"),
+            "cafe\u{0301}"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation and padding of platform strings.
+
+use std::ffi::OsStr;
+
+use crate::{Alignment, UnicodeTruncateStr};
+
+/// Truncates an [`OsStr`] to be at most `max_width` in terms of display width by removing the end
+/// characters. Only available when the `std` feature of this library is activated, and it is
+/// activated by default.
+///
+/// The string is converted with [`OsStr::to_string_lossy`], so invalid sequences are displayed as
+/// U+FFFD REPLACEMENT CHARACTER, and then truncated like
+/// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate). This is useful for displaying
+/// file names, which are not guaranteed to be valid UTF-8.
+///
+/// # Arguments
+/// * `s` - the string to truncate
+/// * `max_width` - the maximum display width
+///
+/// # Examples
+/// ```rust
+/// use std::ffi::OsStr;
+/// use unicode_truncate::unicode_truncate_os;
+///
+/// assert_eq!(unicode_truncate_os(OsStr::new("你好吗.txt"), 5), ("你好".to_string(), 4));
+/// ```
+pub fn unicode_truncate_os(s: &OsStr, max_width: usize) -> (String, usize) {
+    let lossy = s.to_string_lossy();
+    let (truncated, width) = lossy.unicode_truncate(max_width);
+    (truncated.to_owned(), width)
+}
+
+/// Pads an [`OsStr`] to be `target_width` in terms of display width. Only available when the `std`
+/// feature of this library is activated, and it is activated by default.
+///
+/// The string is converted with [`OsStr::to_string_lossy`] like in [`unicode_truncate_os`], and
+/// then padded like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad).
+///
+/// # Arguments
+/// * `s` - the string to pad
+/// * `target_width` - the display width to pad to
+/// * `align` - alignment for truncation and padding
+/// * `truncate` - whether to truncate string if necessary
+///
+/// # Examples
+/// ```rust
+/// use std::ffi::OsStr;
+/// use unicode_truncate::{unicode_pad_os, Alignment};
+///
+/// assert_eq!(unicode_pad_os(OsStr::new("你好"), 6, Alignment::Right, true), "  你好");
+/// ```
+pub fn unicode_pad_os(s: &OsStr, target_width: usize, align: Alignment, truncate: bool) -> String {
+    s.to_string_lossy()
+        .unicode_pad(target_width, align, truncate)
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_unicode() {
+        let name = OsStr::new("你好吗.txt");
+        for max_width in 0..12 {
+            let (truncated, width) = "你好吗.txt".unicode_truncate(max_width);
+            assert_eq!(
+                unicode_truncate_os(name, max_width),
+                (truncated.to_owned(), width)
+            );
+        }
+        assert_eq!(
+            unicode_pad_os(name, 12, Alignment::Left, true),
+            "你好吗.txt  "
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"ab\xFFcd");
+        assert_eq!(unicode_truncate_os(name, 3), ("ab\u{FFFD}".to_owned(), 3));
+        assert_eq!(
+            unicode_pad_os(name, 6, Alignment::Right, true),
+            " ab\u{FFFD}cd"
+        );
+    }
+}
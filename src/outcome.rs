@@ -0,0 +1,90 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Outcome of truncation compared to the requested width.
+
+/// How the result of truncation relates to the requested width, returned by
+/// [`unicode_truncate_full`](crate::UnicodeTruncateStr::unicode_truncate_full) and its variants.
+///
+/// This tells apart a string that was narrow to begin with from one where a wide character at the
+/// truncation point left some columns unused, which both return a width below `max_width`.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum FitOutcome {
+    /// The string fits in `max_width` and is returned unchanged.
+    Untouched,
+    /// The string was truncated to exactly `max_width`.
+    Exact,
+    /// The string was truncated to less than `max_width`, as the next character would have
+    /// exceeded it.
+    Short {
+        /// The columns of `max_width` left unused by the result.
+        unusable_cols: usize,
+    },
+}
+
+impl FitOutcome {
+    /// Returns the outcome of truncating a string of `len` bytes to `max_width`, given the length
+    /// and width of the result.
+    pub(crate) fn of(len: usize, result_len: usize, result_width: usize, max_width: usize) -> Self {
+        if result_len == len {
+            FitOutcome::Untouched
+        } else if result_width >= max_width {
+            FitOutcome::Exact
+        } else {
+            FitOutcome::Short {
+                // unwrap is safe as the result is narrower than max_width
+                unusable_cols: max_width.checked_sub(result_width).unwrap(),
+            }
+        }
+    }
+
+    /// Returns the number of columns of `max_width` left unused by truncation, which is 0 unless
+    /// the outcome is [`Short`](FitOutcome::Short).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateStr;
+    ///
+    /// assert_eq!("你好吗".unicode_truncate_full(5).2.unusable_cols(), 1);
+    /// assert_eq!("你好".unicode_truncate_full(5).2.unusable_cols(), 0);
+    /// ```
+    #[inline]
+    pub fn unusable_cols(self) -> usize {
+        match self {
+            FitOutcome::Short { unusable_cols } => unusable_cols,
+            FitOutcome::Untouched | FitOutcome::Exact => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untouched() {
+        assert_eq!(FitOutcome::of(6, 6, 4, 5), FitOutcome::Untouched);
+        assert_eq!(FitOutcome::of(0, 0, 0, 0), FitOutcome::Untouched);
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!(FitOutcome::of(9, 6, 4, 4), FitOutcome::Exact);
+        assert_eq!(FitOutcome::of(9, 0, 0, 0), FitOutcome::Exact);
+    }
+
+    #[test]
+    fn short() {
+        assert_eq!(
+            FitOutcome::of(9, 6, 4, 5),
+            FitOutcome::Short { unusable_cols: 1 }
+        );
+        assert_eq!(FitOutcome::Short { unusable_cols: 1 }.unusable_cols(), 1);
+    }
+}
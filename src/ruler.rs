@@ -0,0 +1,104 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rendering of the column grid of a string, for debugging.
+
+use std::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Renders the column ruler described in
+/// [`unicode_width_ruler`](crate::UnicodeTruncateStr::unicode_width_ruler).
+pub(crate) fn ruler(s: &str) -> String {
+    let mut text = String::with_capacity(s.len());
+    let mut cells = String::new();
+    let mut zero_width = String::new();
+    let mut column = 0usize;
+    for grapheme in s.graphemes(true) {
+        let width = grapheme.width();
+        if width == 0 {
+            // unwrap is safe as writing to a String never fails
+            write!(zero_width, "\ncolumn {column}: zero-width").unwrap();
+            for ch in grapheme.chars() {
+                write!(zero_width, " U+{:04X}", u32::from(ch)).unwrap();
+            }
+        }
+
+        if grapheme.chars().any(char::is_control) {
+            text.extend(core::iter::repeat('\u{FFFD}').take(width));
+        } else {
+            text.push_str(grapheme);
+        }
+        match width {
+            0 => {}
+            1 => cells.push('^'),
+            _ => {
+                cells.push('<');
+                cells.extend(core::iter::repeat('-').take(width.saturating_sub(2)));
+                cells.push('>');
+            }
+        }
+        column = column.saturating_add(width);
+    }
+
+    let mut result = String::new();
+    if column > 10 {
+        result.extend((0..column).map(|col| match col % 10 {
+            // unwrap is safe as the digit is less than 10
+            0 => char::from_digit((col / 10 % 10) as u32, 10).unwrap(),
+            _ => ' ',
+        }));
+        result.push('\n');
+    }
+    // unwrap is safe as the digit is less than 10
+    result.extend((0..column).map(|col| char::from_digit((col % 10) as u32, 10).unwrap()));
+    result.push('\n');
+    result.push_str(&text);
+    result.push('\n');
+    result.push_str(&cells);
+    result.push_str(&zero_width);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(ruler(""), "\n\n");
+    }
+
+    #[test]
+    fn narrow_and_wide() {
+        assert_eq!(ruler("a你b"), "0123\na你b\n^<>^");
+    }
+
+    #[test]
+    fn zero_width() {
+        assert_eq!(
+            ruler("a\u{0306}\u{200B}b"),
+            "01\na\u{0306}\u{200B}b\n^^\ncolumn 1: zero-width U+200B"
+        );
+    }
+
+    #[test]
+    fn control_replaced() {
+        assert_eq!(ruler("a\u{19}"), "01\na\u{FFFD}\n^^");
+    }
+
+    #[test]
+    fn tens() {
+        assert_eq!(
+            ruler("你好吗你好吗"),
+            "0         1 \n012345678901\n你好吗你好吗\n<><><><><><>"
+        );
+    }
+}
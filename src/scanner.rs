@@ -0,0 +1,289 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Incremental start truncation for strings that only grow at the end.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::UnicodeTruncateStr;
+
+/// Saved progress of a [`StartTruncateScanner`].
+///
+/// The state can be extracted with [`StartTruncateScanner::state`] and later handed to
+/// [`StartTruncateScanner::resume`] together with a longer string that has the previously scanned
+/// one as its prefix.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct ScanState {
+    /// The `max_width` the state was computed for, `None` if nothing was computed yet.
+    max_width: Option<usize>,
+    /// Length in bytes of the source that was scanned.
+    scanned: usize,
+    /// Byte index of the last grapheme in the scanned source.
+    last_start: usize,
+    /// Display width of the last grapheme in the scanned source.
+    last_width: usize,
+    /// Byte index where the truncated result starts.
+    start: usize,
+    /// Display width of the truncated result.
+    width: usize,
+}
+
+/// Truncates a string from the start like
+/// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start), reusing the
+/// previous scan when the string has only been appended to.
+///
+/// This is useful for streaming output, e.g. a scrolling log view that always shows the tail of a
+/// growing buffer. Instead of rescanning the whole string on every append, only the appended part
+/// and the graphemes dropped from the start are visited.
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::StartTruncateScanner;
+///
+/// let log = "你好吗";
+/// let mut scanner = StartTruncateScanner::new(&log[..6]);
+/// assert_eq!(scanner.truncated_at(3), ("好", 2));
+///
+/// let mut scanner = StartTruncateScanner::resume(log, scanner.state());
+/// assert_eq!(scanner.truncated_at(3), ("吗", 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StartTruncateScanner<'a> {
+    src: &'a str,
+    state: ScanState,
+}
+
+impl<'a> StartTruncateScanner<'a> {
+    /// Creates a scanner without any previous state.
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            state: ScanState::default(),
+        }
+    }
+
+    /// Creates a scanner continuing from `state`.
+    ///
+    /// The state must come from a scanner over a prefix of `src`, i.e. `src` was only appended to
+    /// since. If `src` is shorter than the scanned source, or the scanned length or one of the
+    /// saved positions in it is not at a char boundary of `src`, the state is discarded and the
+    /// next call starts from scratch. Other modifications of the prefix are not detected and
+    /// produce unspecified (but memory safe) results.
+    pub fn resume(src: &'a str, state: ScanState) -> Self {
+        let at_boundary =
+            |byte_index: usize| byte_index <= state.scanned && src.is_char_boundary(byte_index);
+        let state = if state.scanned <= src.len()
+            && src.is_char_boundary(state.scanned)
+            && at_boundary(state.last_start)
+            && at_boundary(state.start)
+        {
+            state
+        } else {
+            ScanState::default()
+        };
+        Self { src, state }
+    }
+
+    /// Returns the current scan state, to be passed to [`resume`](Self::resume) later.
+    pub fn state(&self) -> ScanState {
+        self.state
+    }
+
+    /// Returns the source string.
+    pub fn source(&self) -> &'a str {
+        self.src
+    }
+
+    /// Truncates the source to be at most `max_width` in terms of display width by removing the
+    /// start characters.
+    ///
+    /// The result is the same as
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start). Changing
+    /// `max_width` between calls triggers a full rescan.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    pub fn truncated_at(&mut self, max_width: usize) -> (&'a str, usize) {
+        if self.state.max_width == Some(max_width) {
+            self.rescan_appended(max_width);
+        } else {
+            self.full_scan(max_width);
+        }
+
+        // unwrap is safe as the index comes from grapheme_indices
+        let result = self.src.get(self.state.start..).unwrap();
+        crate::check_width(result, self.state.width);
+        (result, self.state.width)
+    }
+
+    fn full_scan(&mut self, max_width: usize) {
+        let (result, width) = self.src.unicode_truncate_start(max_width);
+        let (last_start, last_width) = self
+            .src
+            .grapheme_indices(true)
+            .next_back()
+            .map_or((0, 0), |(byte_index, grapheme)| {
+                (byte_index, grapheme.width())
+            });
+        self.state = ScanState {
+            max_width: Some(max_width),
+            scanned: self.src.len(),
+            last_start,
+            last_width,
+            // unwrap is safe as the result is a suffix of src
+            start: self.src.len().checked_sub(result.len()).unwrap(),
+            width,
+        };
+    }
+
+    fn rescan_appended(&mut self, max_width: usize) {
+        let state = self.state;
+        if state.scanned == self.src.len() {
+            return;
+        }
+
+        // The appended part may merge with the last grapheme of the previous scan, so segment again
+        // from there. When the previous result dropped that grapheme, the old start is past it and
+        // might not be a grapheme boundary anymore.
+        let (mut start, mut width) = if state.last_start < state.start {
+            (state.last_start, 0)
+        } else {
+            // unwrap is safe as the last grapheme is part of the previous result
+            (
+                state.start,
+                state.width.checked_sub(state.last_width).unwrap(),
+            )
+        };
+
+        let mut last = (state.last_start, 0);
+        for (byte_index, grapheme) in self.src[state.last_start..].grapheme_indices(true) {
+            let grapheme_width = grapheme.width();
+            width = width.saturating_add(grapheme_width);
+            // unwrap is safe as the sum is at most the length of src
+            last = (
+                state.last_start.checked_add(byte_index).unwrap(),
+                grapheme_width,
+            );
+        }
+
+        // drop graphemes from the start until the rest fits
+        if width > max_width {
+            let kept = self.src[start..]
+                .grapheme_indices(true)
+                .find(|&(_, grapheme)| {
+                    let fits = width <= max_width;
+                    if !fits {
+                        // width is the sum of the graphemes being dropped, unless the state was
+                        // resumed on a modified source
+                        width = width.saturating_sub(grapheme.width());
+                    }
+                    fits
+                })
+                .map(|(byte_index, _)| byte_index);
+            // unwrap is safe as the sum is at most the length of src
+            start = kept.map_or(self.src.len(), |byte_index| {
+                start.checked_add(byte_index).unwrap()
+            });
+        }
+
+        // like in unicode_truncate_start, a truncated result does not start with a detached grapheme
+        if start > 0 {
+            let (rest, rest_width) =
+                crate::trim_detached_start(&self.src[start..], width, UnicodeWidthStr::width);
+            // unwrap is safe as the rest is a suffix of src
+            start = self.src.len().checked_sub(rest.len()).unwrap();
+            width = rest_width;
+        }
+
+        self.state = ScanState {
+            max_width: Some(max_width),
+            scanned: self.src.len(),
+            last_start: last.0,
+            last_width: last.1,
+            start,
+            width,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `input` to a scanner in increasing prefixes and compares with the full scan.
+    fn assert_matches_full_scan(input: &str, max_width: usize) {
+        let mut state = ScanState::default();
+        for (end, _) in input.char_indices().skip(1).chain(Some((input.len(), ' '))) {
+            let src = &input[..end];
+            let mut scanner = StartTruncateScanner::resume(src, state);
+            assert_eq!(
+                scanner.truncated_at(max_width),
+                src.unicode_truncate_start(max_width),
+                "{src:?} at width {max_width}"
+            );
+            state = scanner.state();
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(StartTruncateScanner::new("").truncated_at(4), ("", 0));
+    }
+
+    #[test]
+    fn same_as_full_scan() {
+        for input in [
+            "boundary",
+            "你好吗你好吗",
+            "y\u{0306}ey\u{0306}s",
+            "123👨‍👩‍👧‍👦456",
+            "🇺🇸🇺🇸a🇺",
+            "a\u{0301}\u{0301}你\u{200B}b",
+        ] {
+            for max_width in 0..10 {
+                assert_matches_full_scan(input, max_width);
+            }
+        }
+    }
+
+    #[test]
+    fn appended_char_joins_last_grapheme() {
+        let mut scanner = StartTruncateScanner::new("ab你");
+        assert_eq!(scanner.truncated_at(1), ("", 0));
+        let mut scanner = StartTruncateScanner::resume("ab你\u{0306}", scanner.state());
+        assert_eq!(scanner.truncated_at(1), ("", 0));
+        let mut scanner = StartTruncateScanner::resume("ab你\u{0306}c", scanner.state());
+        assert_eq!(scanner.truncated_at(1), ("c", 1));
+    }
+
+    #[test]
+    fn width_changed() {
+        let mut scanner = StartTruncateScanner::new("你好吗");
+        assert_eq!(scanner.truncated_at(4), ("好吗", 4));
+        assert_eq!(scanner.truncated_at(2), ("吗", 2));
+    }
+
+    #[test]
+    fn invalid_state_is_discarded() {
+        let mut scanner = StartTruncateScanner::new("你好吗");
+        assert_eq!(scanner.truncated_at(4), ("好吗", 4));
+        let mut scanner = StartTruncateScanner::resume("你", scanner.state());
+        assert_eq!(scanner.truncated_at(4), ("你", 2));
+    }
+
+    #[test]
+    fn state_of_other_source_is_discarded() {
+        let mut scanner = StartTruncateScanner::new("abcdefghi");
+        assert_eq!(scanner.truncated_at(1), ("i", 1));
+        // the saved start is in the middle of a char of the new source
+        let mut scanner = StartTruncateScanner::resume("aaaaaa你b", scanner.state());
+        assert_eq!(scanner.truncated_at(1), ("b", 1));
+    }
+}
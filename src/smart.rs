@@ -0,0 +1,163 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation preferring word boundaries or other break opportunities.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::UnicodeTruncateStr;
+
+/// Configuration for
+/// [`unicode_truncate_smart`](crate::UnicodeTruncateStr::unicode_truncate_smart).
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::SmartTruncateConfig;
+///
+/// let config = SmartTruncateConfig {
+///     min_acceptable_fraction: 0.5,
+///     ..SmartTruncateConfig::default()
+/// };
+/// assert!(config.prefer_word_boundary);
+/// ```
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct SmartTruncateConfig {
+    /// Whether to try truncating at a word boundary first. When `false`, the string is always
+    /// truncated at the exact width.
+    pub prefer_word_boundary: bool,
+    /// The minimum fraction of `max_width` the word boundary truncation has to fill to be used,
+    /// e.g. `0.75` to fall back to the exact truncation if more than a quarter of the width would
+    /// be left empty.
+    pub min_acceptable_fraction: f32,
+}
+
+impl Default for SmartTruncateConfig {
+    /// Prefers word boundaries if they fill at least 75% of the width.
+    fn default() -> Self {
+        Self {
+            prefer_word_boundary: true,
+            min_acceptable_fraction: 0.75,
+        }
+    }
+}
+
+pub(crate) fn truncate_smart(
+    s: &str,
+    max_width: usize,
+    config: SmartTruncateConfig,
+) -> (&str, usize) {
+    let exact = s.unicode_truncate(max_width);
+    if !config.prefer_word_boundary || exact.0.len() == s.len() {
+        return exact;
+    }
+
+    let words = truncate_at_word_boundary(s, exact.0.len());
+    if (words.1 as f32) < config.min_acceptable_fraction * max_width as f32 {
+        exact
+    } else {
+        words
+    }
+}
+
+/// Truncates `s` at the last word boundary at or before the byte index `end`, dropping trailing
+/// whitespace.
+pub(crate) fn truncate_at_word_boundary(s: &str, end: usize) -> (&str, usize) {
+    let boundary = s
+        .split_word_bound_indices()
+        .map(|(byte_index, _)| byte_index)
+        .take_while(|&byte_index| byte_index <= end)
+        .last()
+        .unwrap_or(0);
+    let result = s[..boundary].trim_end();
+    (result, crate::graphemes_width(result))
+}
+
+/// Truncates `s` right before the last grapheme starting with one of `breakpoints` that leaves a
+/// prefix fitting in `max_width`, or at the exact width with `fallback` if there is none.
+pub(crate) fn truncate_at_breakpoints<'a>(
+    s: &'a str,
+    max_width: usize,
+    breakpoints: &[char],
+    fallback: bool,
+) -> (&'a str, usize) {
+    let exact = s.unicode_truncate(max_width);
+    if exact.0.len() == s.len() {
+        return exact;
+    }
+    let is_breakpoint = |grapheme: &str| {
+        grapheme
+            .chars()
+            .next()
+            .map_or(false, |c| breakpoints.contains(&c))
+    };
+    if s[exact.0.len()..]
+        .graphemes(true)
+        .next()
+        .map_or(false, is_breakpoint)
+    {
+        return exact;
+    }
+
+    let mut last_breakpoint = None;
+    let mut width = 0usize;
+    for (byte_index, grapheme) in exact.0.grapheme_indices(true) {
+        if is_breakpoint(grapheme) {
+            last_breakpoint = Some((byte_index, width));
+        }
+        width = width.saturating_add(grapheme.width());
+    }
+    match last_breakpoint {
+        Some((byte_index, width)) => (&s[..byte_index], width),
+        None if fallback => exact,
+        None => ("", 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_boundary() {
+        assert_eq!(truncate_at_word_boundary("hello world", 8), ("hello", 5));
+        assert_eq!(truncate_at_word_boundary("hello world", 5), ("hello", 5));
+        assert_eq!(truncate_at_word_boundary("hello world", 6), ("hello", 5));
+        assert_eq!(truncate_at_word_boundary("hello world", 3), ("", 0));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let path = "/usr/share/doc";
+        assert_eq!(
+            truncate_at_breakpoints(path, 12, &['/'], false),
+            ("/usr/share", 10)
+        );
+        assert_eq!(
+            truncate_at_breakpoints(path, 10, &['/'], false),
+            ("/usr/share", 10)
+        );
+        assert_eq!(truncate_at_breakpoints(path, 9, &['/'], false), ("/usr", 4));
+        assert_eq!(truncate_at_breakpoints(path, 3, &['/'], false), ("", 0));
+        assert_eq!(truncate_at_breakpoints(path, 20, &['/'], false), (path, 14));
+    }
+
+    #[test]
+    fn no_breakpoint_fits() {
+        assert_eq!(
+            truncate_at_breakpoints("12:34:56", 1, &[':'], false),
+            ("", 0)
+        );
+        assert_eq!(
+            truncate_at_breakpoints("12:34:56", 1, &[':'], true),
+            ("1", 1)
+        );
+        assert_eq!(truncate_at_breakpoints("你好吗", 5, &[], true), ("你好", 4));
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation refusing characters of unknown width.
+
+use core::fmt;
+
+use unicode_width::UnicodeWidthChar;
+
+/// The error returned by
+/// [`unicode_truncate_strict_width`](crate::UnicodeTruncateStr::unicode_truncate_strict_width)
+/// when the result contains a character without a defined display width, like a control
+/// character.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct UnknownWidthChar {
+    offset: usize,
+    character: char,
+}
+
+impl UnknownWidthChar {
+    /// Returns the byte offset of the character in the truncated string.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the character without a defined display width.
+    pub fn character(&self) -> char {
+        self.character
+    }
+}
+
+impl fmt::Display for UnknownWidthChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "character {:?} at byte {} has no defined display width",
+            self.character, self.offset
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownWidthChar {}
+
+/// Returns an error for the first character of `s` that [`UnicodeWidthChar::width`] returns `None`
+/// for.
+pub(crate) fn check_known_width(s: &str) -> Result<(), UnknownWidthChar> {
+    match s.char_indices().find(|(_, c)| c.width().is_none()) {
+        Some((offset, character)) => Err(UnknownWidthChar { offset, character }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_width() {
+        assert_eq!(check_known_width(""), Ok(()));
+        assert_eq!(check_known_width("a你\u{200B}e\u{301}"), Ok(()));
+    }
+
+    #[test]
+    fn first_unknown_width() {
+        let err = check_known_width("a你\u{7}\n").unwrap_err();
+        assert_eq!((err.offset(), err.character()), (4, '\u{7}'));
+    }
+}
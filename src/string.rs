@@ -0,0 +1,217 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation of owned strings in place.
+
+use crate::{split_fill, UnicodeTruncateStr};
+
+/// Methods for truncating owned strings in place using displayed width. Only available when the
+/// `std` feature of this library is activated, and it is activated by default.
+pub trait UnicodeTruncateString {
+    /// Truncates a string in place to be at most `max_width` in terms of display width by removing
+    /// the end characters.
+    ///
+    /// The string keeps exactly what
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) returns for it, and no
+    /// memory is allocated or released. Returns the display width of the truncated string, and
+    /// whether anything was removed.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateString;
+    ///
+    /// let mut s = String::from("你好吗");
+    /// assert_eq!(s.unicode_truncate_in_place(5), (4, true));
+    /// assert_eq!(s, "你好");
+    /// assert_eq!(s.unicode_truncate_in_place(5), (4, false));
+    /// ```
+    fn unicode_truncate_in_place(&mut self, max_width: usize) -> (usize, bool);
+
+    /// Truncates a string in place to be at most `max_width` in terms of display width by removing
+    /// the start characters.
+    ///
+    /// The string keeps exactly what
+    /// [`unicode_truncate_start`](crate::UnicodeTruncateStr::unicode_truncate_start) returns for
+    /// it, including how zero-width characters at the boundary are removed. The kept bytes are
+    /// moved to the front of the existing buffer, so no memory is allocated or released, and
+    /// nothing is moved when nothing is removed. Returns the display width of the truncated string.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateString;
+    ///
+    /// let mut s = String::from("你好吗");
+    /// assert_eq!(s.unicode_truncate_start_in_place(5), 4);
+    /// assert_eq!(s, "好吗");
+    /// ```
+    fn unicode_truncate_start_in_place(&mut self, max_width: usize) -> usize;
+
+    /// Makes a string exactly `target_width` in terms of display width in place, by truncating the
+    /// end or appending `fill`.
+    ///
+    /// The string is truncated like
+    /// [`unicode_truncate_in_place`](UnicodeTruncateString::unicode_truncate_in_place), and then
+    /// padded on the right like [`unicode_pad`](crate::UnicodeTruncateStr::unicode_pad) with left
+    /// alignment, including the column a wide character at the truncation point leaves unused. The
+    /// existing capacity is reused, so resizing a string to a width it has been resized to before
+    /// does not allocate. When `fill` is wide, the columns it can not cover are filled with spaces
+    /// at the end, so the string is always `target_width` columns wide.
+    ///
+    /// # Arguments
+    /// * `target_width` - the display width to resize to
+    /// * `fill` - the character to pad with
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateString;
+    ///
+    /// let mut cell = String::from("你好吗");
+    /// cell.unicode_resize_width(5, '.');
+    /// assert_eq!(cell, "你好.");
+    /// cell.unicode_resize_width(8, '.');
+    /// assert_eq!(cell, "你好....");
+    /// ```
+    fn unicode_resize_width(&mut self, target_width: usize, fill: char);
+}
+
+impl UnicodeTruncateString for String {
+    #[inline]
+    fn unicode_truncate_in_place(&mut self, max_width: usize) -> (usize, bool) {
+        let (truncated, width) = self.unicode_truncate(max_width);
+        let len = truncated.len();
+        let removed = len < self.len();
+        self.truncate(len);
+        (width, removed)
+    }
+
+    #[inline]
+    fn unicode_truncate_start_in_place(&mut self, max_width: usize) -> usize {
+        let (truncated, width) = self.unicode_truncate_start(max_width);
+        // unwrap is safe as the result is a suffix of the string
+        let start = self.len().checked_sub(truncated.len()).unwrap();
+        if start > 0 {
+            self.drain(..start);
+        }
+        width
+    }
+
+    #[inline]
+    fn unicode_resize_width(&mut self, target_width: usize, fill: char) {
+        let (width, _) = self.unicode_truncate_in_place(target_width);
+        // width is at most target_width after truncation
+        let (fills, spaces) = split_fill(target_width.saturating_sub(width), fill);
+        self.extend(core::iter::repeat(fill).take(fills));
+        self.extend(core::iter::repeat(' ').take(spaces));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_capacity() {
+        let mut s = String::with_capacity(32);
+        s.push_str("abc\u{0306}def");
+        assert_eq!(s.unicode_truncate_in_place(3), (3, true));
+        assert_eq!(s, "abc\u{0306}");
+        assert_eq!(s.capacity(), 32);
+    }
+
+    #[test]
+    fn nothing_removed() {
+        let mut s = String::from("\u{200B}\u{200B}");
+        assert_eq!(s.unicode_truncate_in_place(0), (0, false));
+        assert_eq!(s, "\u{200B}\u{200B}");
+        let mut s = String::new();
+        assert_eq!(s.unicode_truncate_in_place(0), (0, false));
+    }
+
+    #[test]
+    fn start_keeps_capacity() {
+        let mut s = String::with_capacity(32);
+        s.push_str("abc\u{0306}def");
+        assert_eq!(s.unicode_truncate_start_in_place(2), 2);
+        assert_eq!(s, "ef");
+        assert_eq!(s.capacity(), 32);
+        // everything removed
+        assert_eq!(s.unicode_truncate_start_in_place(0), 0);
+        assert_eq!(s, "");
+        assert_eq!(s.capacity(), 32);
+    }
+
+    #[test]
+    fn start_nothing_removed() {
+        let mut s = String::from("你好");
+        let ptr = s.as_ptr();
+        assert_eq!(s.unicode_truncate_start_in_place(4), 4);
+        assert_eq!(s, "你好");
+        assert_eq!(s.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn start_zero_width_at_boundary() {
+        // the ZWSP and the orphaned mark at the cut are removed like by unicode_truncate_start
+        for input in ["a\u{200B}bc", "a\u{0306}bc", "你\u{0306}bc"] {
+            let mut s = String::from(input);
+            let width = s.unicode_truncate_start_in_place(2);
+            assert_eq!((s.as_str(), width), input.unicode_truncate_start(2));
+            assert_eq!(s, "bc");
+        }
+    }
+
+    #[test]
+    fn resize_exact_width() {
+        use unicode_width::UnicodeWidthStr;
+
+        for input in ["", "abc", "你好吗", "a你b", "y\u{0306}es", "\u{200B}"] {
+            for fill in [' ', '.', '＊'] {
+                for target_width in 0..8 {
+                    let mut s = String::from(input);
+                    s.unicode_resize_width(target_width, fill);
+                    assert_eq!(s.width(), target_width, "{:?}", s);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_same_as_pad() {
+        use crate::Alignment;
+
+        for input in ["abc", "你好吗", "y\u{0306}es"] {
+            for target_width in 0..8 {
+                let mut s = String::from(input);
+                s.unicode_resize_width(target_width, ' ');
+                assert_eq!(s, input.unicode_pad(target_width, Alignment::Left, true));
+            }
+        }
+    }
+
+    #[test]
+    fn resize_reuses_capacity() {
+        // warm up with the longest content
+        let mut s = String::from("你好吗你好吗");
+        s.unicode_resize_width(8, '.');
+        let ptr = s.as_ptr();
+        for input in ["abc", "你好吗你好吗", ""] {
+            s.clear();
+            s.push_str(input);
+            s.unicode_resize_width(8, '.');
+            assert_eq!(s.as_ptr(), ptr);
+        }
+        assert_eq!(s, "........");
+    }
+}
@@ -0,0 +1,469 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncation of character and string streams.
+
+use core::iter::{FusedIterator, Peekable};
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::UnicodeTruncateStr;
+
+/// Capacity in bytes of the buffer holding one grapheme cluster.
+const CLUSTER_CAPACITY: usize = 64;
+
+/// Creates an iterator adapter yielding the characters of `iter` until the display width would
+/// exceed `max_width`.
+///
+/// This truncates like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) for text
+/// that is not materialized as a `&str`, e.g. text decoded incrementally or stored in a rope. The
+/// characters are grouped into grapheme clusters, and each cluster is either yielded entirely or
+/// withheld, so a ZWJ sequence is never cut in the middle. Zero-width clusters are yielded as long
+/// as the budget is not exceeded.
+///
+/// To find the end of a cluster, the adapter reads one character ahead from `iter`. So when it
+/// stops, the withheld cluster and possibly the character after it have been consumed from `iter`.
+/// Clusters longer than 64 bytes in UTF-8 are split into pieces, which only happens with unusual
+/// text like long sequences of combining marks.
+///
+/// # Arguments
+/// * `iter` - the characters to truncate
+/// * `max_width` - the maximum display width
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::take_width;
+///
+/// let mut taken = take_width("你好吗".chars(), 5);
+/// assert_eq!(taken.by_ref().collect::<String>(), "你好");
+/// assert_eq!(taken.columns_taken(), 4);
+/// ```
+pub fn take_width<I: Iterator<Item = char>>(iter: I, max_width: usize) -> TakeWidth<I> {
+    TakeWidth {
+        iter,
+        max_width,
+        taken: 0,
+        lookahead: None,
+        cluster: [0; CLUSTER_CAPACITY],
+        cluster_len: 0,
+        yielded: 0,
+        done: false,
+    }
+}
+
+/// Iterator adapter yielding characters until a display width budget is exhausted.
+///
+/// Created by [`take_width`].
+#[derive(Debug, Clone)]
+pub struct TakeWidth<I> {
+    iter: I,
+    max_width: usize,
+    taken: usize,
+    /// The character read after the current cluster, starting the next one.
+    lookahead: Option<char>,
+    /// The current cluster encoded as UTF-8.
+    cluster: [u8; CLUSTER_CAPACITY],
+    cluster_len: usize,
+    /// Length in bytes of the part of the current cluster already yielded.
+    yielded: usize,
+    done: bool,
+}
+
+impl<I> TakeWidth<I> {
+    /// Returns the display width of the characters accepted so far, including the cluster being
+    /// yielded.
+    pub fn columns_taken(&self) -> usize {
+        self.taken
+    }
+
+    fn cluster(&self) -> &str {
+        encoded(&self.cluster[..self.cluster_len])
+    }
+}
+
+fn encoded(buf: &[u8]) -> &str {
+    // unwrap is safe as only whole chars are encoded into the buffer
+    core::str::from_utf8(buf).unwrap()
+}
+
+impl<I: Iterator<Item = char>> TakeWidth<I> {
+    /// Reads the next cluster into the buffer, returns `false` if `iter` is exhausted.
+    fn read_cluster(&mut self) -> bool {
+        let first = match self.lookahead.take().or_else(|| self.iter.next()) {
+            Some(first) => first,
+            None => return false,
+        };
+        self.cluster_len = first.encode_utf8(&mut self.cluster).len();
+        self.yielded = 0;
+
+        for next in self.iter.by_ref() {
+            let len = self.cluster_len;
+            // unwrap is safe as a char is at most 4 bytes
+            let new_len = len.checked_add(next.len_utf8()).unwrap();
+            if new_len > CLUSTER_CAPACITY {
+                self.lookahead = Some(next);
+                break;
+            }
+            next.encode_utf8(&mut self.cluster[len..]);
+            self.cluster_len = new_len;
+            // a boundary only depends on the preceding characters and the next one, so it can not
+            // disappear once the next character is known
+            let first_len = encoded(&self.cluster[..new_len])
+                .graphemes(true)
+                .next()
+                .map_or(0, str::len);
+            if first_len <= len {
+                self.cluster_len = len;
+                self.lookahead = Some(next);
+                break;
+            }
+        }
+        true
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for TakeWidth<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.yielded == self.cluster_len {
+            if self.done || !self.read_cluster() {
+                self.done = true;
+                self.cluster_len = 0;
+                self.yielded = 0;
+                return None;
+            }
+            match self.taken.checked_add(self.cluster().width()) {
+                Some(taken) if taken <= self.max_width => self.taken = taken,
+                _ => {
+                    self.done = true;
+                    self.cluster_len = 0;
+                    self.yielded = 0;
+                    return None;
+                }
+            }
+        }
+
+        // unwrap is safe as the cluster is not fully yielded
+        let ch = self.cluster()[self.yielded..].chars().next().unwrap();
+        // unwrap is safe as the sum is at most cluster_len
+        self.yielded = self.yielded.checked_add(ch.len_utf8()).unwrap();
+        Some(ch)
+    }
+}
+
+impl<I: Iterator<Item = char>> FusedIterator for TakeWidth<I> {}
+
+/// Creates an iterator adapter yielding strings of `iter` as long as they fit in `budget` in terms
+/// of display width, together with a separator of `sep_width` columns between them.
+///
+/// This is a building block for lists that show as many items as fit. Each item is measured grapheme
+/// by grapheme like [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate) measures its
+/// result, and the separator is only counted between yielded items. The first item that does not
+/// fit is peeked but not consumed, so it can be taken from [`into_inner`](TakeWhileFits::into_inner)
+/// to render it truncated or to count the rest. After that, nothing more is yielded. The adapter
+/// does not allocate and is available without the `std` feature.
+///
+/// # Arguments
+/// * `iter` - the strings to take
+/// * `sep_width` - the display width of the separator between the strings
+/// * `budget` - the maximum display width of the strings and separators
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::take_while_fits;
+///
+/// let mut taken = take_while_fits(["rust", "你好", "unicode"].iter().copied(), 2, 12);
+/// assert_eq!(taken.by_ref().collect::<Vec<_>>(), ["rust", "你好"]);
+/// assert_eq!((taken.consumed(), taken.remaining()), (10, 2));
+/// assert_eq!(taken.into_inner().next(), Some("unicode"));
+/// ```
+pub fn take_while_fits<'a, I: Iterator<Item = &'a str>>(
+    iter: I,
+    sep_width: usize,
+    budget: usize,
+) -> TakeWhileFits<'a, I> {
+    TakeWhileFits {
+        iter: iter.peekable(),
+        sep_width,
+        budget,
+        consumed: 0,
+        yielded_any: false,
+        done: false,
+    }
+}
+
+/// Iterator adapter yielding strings while they fit in a display width budget.
+///
+/// Created by [`take_while_fits`].
+#[derive(Debug, Clone)]
+pub struct TakeWhileFits<'a, I: Iterator<Item = &'a str>> {
+    iter: Peekable<I>,
+    sep_width: usize,
+    budget: usize,
+    consumed: usize,
+    yielded_any: bool,
+    done: bool,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> TakeWhileFits<'a, I> {
+    /// Returns the display width of the strings yielded so far and the separators between them.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Returns the display width left in the budget.
+    pub fn remaining(&self) -> usize {
+        // consumed never exceeds the budget
+        self.budget.saturating_sub(self.consumed)
+    }
+
+    /// Returns the underlying iterator, starting with the first string that did not fit, if any.
+    pub fn into_inner(self) -> Peekable<I> {
+        self.iter
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Iterator for TakeWhileFits<'a, I> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.done {
+            return None;
+        }
+        let item = match self.iter.peek() {
+            Some(item) => *item,
+            None => {
+                self.done = true;
+                return None;
+            }
+        };
+        let sep_width = if self.yielded_any { self.sep_width } else { 0 };
+        let width = crate::graphemes_width(item);
+        match self
+            .consumed
+            .checked_add(sep_width)
+            .and_then(|consumed| consumed.checked_add(width))
+        {
+            Some(consumed) if consumed <= self.budget => {
+                self.consumed = consumed;
+                self.yielded_any = true;
+                self.iter.next()
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> FusedIterator for TakeWhileFits<'a, I> {}
+
+/// Extension methods for truncating the strings of an iterator using displayed width.
+pub trait UnicodeTruncateIter<'a>: Iterator<Item = &'a str> + Sized {
+    /// Creates an iterator adapter truncating each string to be at most `max_width` in terms of
+    /// display width by removing the end characters.
+    ///
+    /// Each string is truncated lazily like
+    /// [`unicode_truncate`](crate::UnicodeTruncateStr::unicode_truncate), and yielded together with
+    /// its display width.
+    ///
+    /// # Arguments
+    /// * `max_width` - the maximum display width
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::UnicodeTruncateIter;
+    ///
+    /// let cells = ["abcdef", "你好吗", "ab"];
+    /// let truncated: Vec<_> = cells.iter().copied().truncate_width(5).collect();
+    /// assert_eq!(truncated, [("abcde", 5), ("你好", 4), ("ab", 2)]);
+    /// ```
+    ///
+    /// Previewing the lines of a file:
+    /// ```rust
+    /// use std::{fs, io, path::Path};
+    /// use unicode_truncate::UnicodeTruncateIter;
+    ///
+    /// fn preview(path: &Path) -> io::Result<()> {
+    ///     let text = fs::read_to_string(path)?;
+    ///     for (line, width) in text.lines().truncate_width(20) {
+    ///         println!("{}{}|", line, " ".repeat(20 - width));
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn truncate_width(self, max_width: usize) -> TruncateWidth<Self> {
+        TruncateWidth {
+            iter: self,
+            max_width,
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a str>> UnicodeTruncateIter<'a> for I {}
+
+/// Iterator adapter truncating each string to a display width.
+///
+/// Created by [`truncate_width`](UnicodeTruncateIter::truncate_width).
+#[derive(Debug, Clone)]
+pub struct TruncateWidth<I> {
+    iter: I,
+    max_width: usize,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Iterator for TruncateWidth<I> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|item| item.unicode_truncate(self.max_width))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I: FusedIterator<Item = &'a str>> FusedIterator for TruncateWidth<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnicodeTruncateStr;
+
+    /// Collects into a fixed buffer, returning the used part.
+    fn collect<I: Iterator<Item = char>>(iter: I, buf: &mut [u8]) -> &str {
+        let mut len = 0usize;
+        for ch in iter {
+            // unwrap is safe as the buffers in the tests are large enough
+            let end = len.checked_add(ch.len_utf8()).unwrap();
+            ch.encode_utf8(&mut buf[len..end]);
+            len = end;
+        }
+        core::str::from_utf8(&buf[..len]).unwrap()
+    }
+
+    #[test]
+    fn same_as_truncate() {
+        for input in [
+            "",
+            "boundary",
+            "你好吗你好吗",
+            "y\u{0306}ey\u{0306}s",
+            "123👨‍👩‍👧‍👦456",
+            "🇺🇸🇺🇸a🇺",
+            "a\u{0301}\u{0301}你\u{200B}b",
+            "a\r\nb",
+        ] {
+            for max_width in 0..16 {
+                let mut buf = [0; 64];
+                let mut taken = take_width(input.chars(), max_width);
+                let result = collect(taken.by_ref(), &mut buf);
+                assert_eq!(
+                    (result, taken.columns_taken()),
+                    input.unicode_truncate(max_width),
+                    "{input:?} at width {max_width}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn consumes_lookahead() {
+        let mut chars = "ab你cd".chars();
+        let mut buf = [0; 8];
+        assert_eq!(collect(take_width(chars.by_ref(), 3), &mut buf), "ab");
+        // the withheld cluster and the character after it
+        assert_eq!(chars.next(), Some('d'));
+    }
+
+    #[test]
+    fn long_cluster_split() {
+        let mut input = [0; 2 + 2 * 40];
+        let mut len = "a".len();
+        input[0] = b'a';
+        for _ in 0..40 {
+            // unwrap is safe as the buffer is large enough
+            let end = len.checked_add(2).unwrap();
+            '\u{0301}'.encode_utf8(&mut input[len..end]);
+            len = end;
+        }
+        let input = core::str::from_utf8(&input[..len]).unwrap();
+        let mut buf = [0; 128];
+        let mut taken = take_width(input.chars(), 1);
+        assert_eq!(collect(taken.by_ref(), &mut buf), input);
+        assert_eq!(taken.columns_taken(), 1);
+    }
+
+    #[test]
+    fn take_while_fits_separators() {
+        let items = ["ab", "cd", "ef"];
+        // "ab, cd" is 6 columns wide
+        let mut taken = take_while_fits(items.iter().copied(), 2, 6);
+        assert!(taken.by_ref().eq(["ab", "cd"]));
+        assert_eq!((taken.consumed(), taken.remaining()), (6, 0));
+        assert_eq!(taken.into_inner().next(), Some("ef"));
+
+        let mut taken = take_while_fits(items.iter().copied(), 2, 5);
+        assert!(taken.by_ref().eq(["ab"]));
+        assert_eq!((taken.consumed(), taken.remaining()), (2, 3));
+    }
+
+    #[test]
+    fn take_while_fits_stops_at_first_misfit() {
+        // "b" would fit after the wide item, but the iteration stops at it
+        let items = ["a", "你好吗", "b"];
+        let mut taken = take_while_fits(items.iter().copied(), 0, 3);
+        assert!(taken.by_ref().eq(["a"]));
+        assert_eq!(taken.next(), None);
+        let mut rest = taken.into_inner();
+        assert_eq!(rest.next(), Some("你好吗"));
+        assert_eq!(rest.next(), Some("b"));
+    }
+
+    #[test]
+    fn take_while_fits_grapheme_width() {
+        // measured grapheme by grapheme like the truncation results, so the emoji on both sides
+        // of the ZWJ count separately
+        let items = ["\u{1F3FD}\u{200D}\u{1F44B}", "\u{200B}"];
+        let mut taken = take_while_fits(items.iter().copied(), 1, 5);
+        assert!(taken.by_ref().eq(items));
+        assert_eq!(taken.consumed(), 5);
+        let mut taken = take_while_fits(items.iter().copied(), 1, 4);
+        assert!(taken.by_ref().eq(["\u{1F3FD}\u{200D}\u{1F44B}"]));
+        let mut taken = take_while_fits(items.iter().copied(), 0, 3);
+        assert_eq!(taken.next(), None);
+        assert_eq!(taken.remaining(), 3);
+    }
+
+    #[test]
+    fn truncate_width_same_as_truncate() {
+        let items = ["", "abcdef", "你好吗", "y\u{0306}es", "\u{200B}\u{200B}"];
+        for max_width in 0..8 {
+            let truncated = items.iter().copied().truncate_width(max_width);
+            assert_eq!(truncated.size_hint(), (items.len(), Some(items.len())));
+            assert!(truncated.eq(items.iter().map(|s| s.unicode_truncate(max_width))));
+        }
+    }
+
+    #[test]
+    fn truncate_width_composes() {
+        let mut widths = "ab\ncdef\n你好吗"
+            .lines()
+            .truncate_width(3)
+            .map(|(_, width)| width)
+            .filter(|&width| width > 2);
+        assert_eq!(widths.next(), Some(3));
+        assert_eq!(widths.next(), None);
+    }
+}
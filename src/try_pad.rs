@@ -0,0 +1,150 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Padding with a limit on the length of the result.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{pad, split_padding, Alignment, UnicodeTruncateStr};
+
+/// The error returned by [`try_unicode_pad`](crate::UnicodeTruncateStr::try_unicode_pad) when the
+/// padded string would be longer than the limit.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct PadError {
+    required: usize,
+    limit: usize,
+}
+
+impl PadError {
+    /// Returns the length in bytes the padded string would need, or `usize::MAX` if the length
+    /// does not fit in `usize`.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Returns the limit in bytes the padded string was checked against.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for PadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "padded string needs {} bytes but the limit is {}",
+            self.required, self.limit
+        )
+    }
+}
+
+impl std::error::Error for PadError {}
+
+/// The maximum length in bytes of a `String`.
+pub(crate) const MAX_STRING_LEN: usize = isize::MAX as usize;
+
+pub(crate) fn try_pad(
+    s: &str,
+    target_width: usize,
+    align: Alignment,
+    truncate: bool,
+    max_len: usize,
+) -> Result<Cow<'_, str>, PadError> {
+    let (truncated, columns) = s.unicode_truncate(target_width);
+    let (content, left_pad, right_pad) = if !truncate && truncated.len() < s.len() {
+        (s, 0, 0)
+    } else {
+        let (left_pad, right_pad) = split_padding(target_width.saturating_sub(columns), align);
+        (truncated, left_pad, right_pad)
+    };
+
+    // a String can not be longer than isize::MAX bytes
+    let max_len = max_len.min(MAX_STRING_LEN);
+    // checked before padding, so that nothing is allocated for a huge target_width
+    let required = content
+        .len()
+        .saturating_add(left_pad)
+        .saturating_add(right_pad);
+    if required > max_len {
+        return Err(PadError {
+            required,
+            limit: max_len,
+        });
+    }
+
+    if left_pad == 0 && right_pad == 0 {
+        Ok(Cow::Borrowed(content))
+    } else {
+        Ok(Cow::Owned(pad(content, left_pad, right_pad, ' ', None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padded() {
+        assert_eq!(
+            try_pad("你", 4, Alignment::Center, true, 8),
+            Ok(Cow::Borrowed(" 你 "))
+        );
+        assert_eq!(
+            try_pad("你好吗", 3, Alignment::Left, true, 8),
+            Ok(Cow::Borrowed("你 "))
+        );
+        assert_eq!(
+            try_pad("你好吗", 3, Alignment::Left, false, 9),
+            Ok(Cow::Borrowed("你好吗"))
+        );
+    }
+
+    #[test]
+    fn exact_limit() {
+        assert_eq!(
+            try_pad("你", 4, Alignment::Left, true, 5),
+            Ok(Cow::Borrowed("你  "))
+        );
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let err = try_pad("你", 4, Alignment::Left, true, 4).unwrap_err();
+        assert_eq!((err.required(), err.limit()), (5, 4));
+        assert_eq!(
+            try_pad("你好吗", 3, Alignment::Left, false, 8),
+            Err(PadError {
+                required: 9,
+                limit: 8
+            })
+        );
+    }
+
+    #[test]
+    fn huge_target_width() {
+        // fails before allocating anything
+        for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+            assert_eq!(
+                try_pad("abc", usize::MAX, align, true, usize::MAX),
+                Err(PadError {
+                    required: usize::MAX,
+                    limit: MAX_STRING_LEN
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn length_overflow() {
+        // the padding alone is almost usize::MAX bytes, and the content is longer than it is wide
+        let err = try_pad("你", usize::MAX, Alignment::Left, true, usize::MAX).unwrap_err();
+        assert_eq!(err.required(), usize::MAX);
+    }
+}
@@ -0,0 +1,744 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Wrapping text into lines of limited display width.
+
+use core::iter::FusedIterator;
+use std::borrow::Cow;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{Alignment, UnicodeTruncateStr, UnicodeWrapWords};
+
+/// Wraps text into lines of at most `line_width` in terms of display width, breaking at word
+/// boundaries. Only available when the `std` feature of this library is activated, and it is
+/// activated by default.
+///
+/// Lines are filled greedily with words as segmented by Unicode word boundaries, so text without
+/// spaces like CJK can be broken between any two ideographs. Whitespace at the breaks is removed.
+/// A word wider than `line_width` is broken between graphemes, and a single grapheme wider than
+/// `line_width` is put on a line by itself.
+///
+/// Blank lines separate paragraphs and are preserved as empty lines in the result. A single
+/// newline within a paragraph is treated as a space, so lines joined across it are returned as
+/// owned strings, while other lines borrow from `text`.
+///
+/// # Arguments
+/// * `text` - the text to wrap
+/// * `line_width` - the maximum display width of each line
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::unicode_wrap_words;
+///
+/// let text = "The quick brown\nfox jumps.\n\n你好吗";
+/// assert_eq!(
+///     unicode_wrap_words(text, 10),
+///     ["The quick", "brown fox", "jumps.", "", "你好吗"]
+/// );
+/// ```
+pub fn unicode_wrap_words(text: &str, line_width: usize) -> Vec<Cow<'_, str>> {
+    let mut lines = Vec::new();
+    for_each_paragraph(text, |paragraph| match paragraph {
+        Some(paragraph) => wrap_paragraph(paragraph, line_width, false, |line, _| {
+            lines.push(newlines_as_spaces(line))
+        }),
+        None => lines.push(Cow::Borrowed("")),
+    });
+    lines
+}
+
+/// Wraps text into lines of at most `line_width` in terms of display width, breaking at word
+/// boundaries and soft hyphens. Only available when the `std` feature of this library is activated,
+/// and it is activated by default.
+///
+/// This works like [`unicode_wrap_words`], but when a word does not fit, it is broken at the last
+/// soft hyphen (U+00AD) that leaves room for a visible `'-'` on the line, and the `'-'` is
+/// inserted. Only when there is no such soft hyphen the word is broken between graphemes. Soft
+/// hyphens not used for breaking are removed, as are newlines within a paragraph, which are
+/// replaced with spaces.
+///
+/// # Arguments
+/// * `text` - the text to wrap
+/// * `line_width` - the maximum display width of each line, including the inserted hyphens
+///
+/// # Examples
+/// ```rust
+/// use unicode_truncate::unicode_hyphenate_wrap;
+///
+/// let text = "a hy\u{AD}phen\u{AD}ation";
+/// assert_eq!(unicode_hyphenate_wrap(text, 8), ["a hy-", "phen-", "ation"]);
+/// ```
+pub fn unicode_hyphenate_wrap(text: &str, line_width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for_each_paragraph(text, |paragraph| match paragraph {
+        Some(paragraph) => wrap_paragraph(paragraph, line_width, true, |line, hyphenated| {
+            let mut line = newlines_as_spaces(line).replace(SOFT_HYPHEN, "");
+            if hyphenated {
+                line.push('-');
+            }
+            lines.push(line);
+        }),
+        None => lines.push(String::new()),
+    });
+    lines
+}
+
+/// Flows `s` into up to `num_columns` pieces of at most `column_width`, breaking at word
+/// boundaries if possible.
+pub(crate) fn flow_columns(s: &str, column_width: usize, num_columns: usize) -> Vec<(&str, usize)> {
+    let mut lines = UnicodeWrapWords::new(s, column_width);
+    // blank lines do not take a column
+    core::iter::from_fn(|| lines.next_line())
+        .filter(|(line, _)| !line.is_empty())
+        .take(num_columns)
+        .collect()
+}
+
+/// Wraps `s` into up to `max_lines` lines of at most `max_width`, ending the last line with
+/// `ellipsis` if any text is left out, and returns whether it is.
+pub(crate) fn clamp_lines<'a>(
+    s: &'a str,
+    max_width: usize,
+    max_lines: usize,
+    ellipsis: &str,
+) -> (Vec<Cow<'a, str>>, bool) {
+    let mut lines = UnicodeWrapWords::new(s, max_width);
+    let last_index = match max_lines.checked_sub(1) {
+        Some(last_index) => last_index,
+        None => return (Vec::new(), lines.next().is_some()),
+    };
+
+    let mut result = Vec::new();
+    loop {
+        let rest = lines.remainder();
+        let line = match lines.next() {
+            Some(line) => line,
+            None => return (result, false),
+        };
+        if result.len() < last_index || lines.remainder().is_empty() {
+            result.push(Cow::Borrowed(line));
+            continue;
+        }
+
+        // the break of the last line is chosen with the ellipsis taking its columns
+        let (ellipsis, ellipsis_width) = ellipsis.unicode_truncate(max_width);
+        let budget = max_width.saturating_sub(ellipsis_width);
+        let content = match UnicodeWrapWords::new(rest, budget).next_line() {
+            Some((content, width)) if width <= budget => content,
+            // a grapheme wider than the columns left, which is put on a line of its own
+            _ => "",
+        };
+        let mut last = String::with_capacity(content.len().saturating_add(ellipsis.len()));
+        last.push_str(crate::trim_trailing_zwj(content));
+        last.push_str(ellipsis);
+        result.push(Cow::Owned(last));
+        return (result, true);
+    }
+}
+
+/// Text fitted into a box of fixed size by
+/// [`unicode_fit_box`](crate::UnicodeTruncateStr::unicode_fit_box). Only available when the `std`
+/// feature of this library is activated, and it is activated by default.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct BoxFit {
+    /// The lines of the box, each padded to the width of the box.
+    pub lines: Vec<String>,
+    /// Whether any text was left out, because the box is not high enough or a grapheme is wider
+    /// than the box.
+    pub truncated: bool,
+    /// The number of lines holding text, not counting the blank lines added to fill the box.
+    pub used_height: usize,
+}
+
+/// Wraps `s` into up to `height` lines, each padded to exactly `width`.
+pub(crate) fn fit_box(
+    s: &str,
+    width: usize,
+    height: usize,
+    align: Alignment,
+    fill_to_height: bool,
+) -> BoxFit {
+    let (lines, mut truncated) = clamp_lines(s, width, height, "");
+    let used_height = lines.len();
+    let mut lines: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            // only a grapheme wider than the box is cut, which is on a line of its own
+            let (kept, _) = line.unicode_truncate(width);
+            if kept.len() < line.len() {
+                truncated = true;
+            }
+            kept.unicode_pad(width, align, true).into_owned()
+        })
+        .collect();
+    if fill_to_height && lines.len() < height {
+        lines.resize(height, "".unicode_pad(width, align, true).into_owned());
+    }
+    BoxFit {
+        lines,
+        truncated,
+        used_height,
+    }
+}
+
+/// Iterator over the lines of a string wrapped with a hanging indent.
+///
+/// Created by [`unicode_wrap_indented`](crate::UnicodeTruncateStr::unicode_wrap_indented). The
+/// first line borrows from the string, while each continuation line is allocated with the prefix
+/// in front of it.
+#[derive(Debug, Clone)]
+pub struct UnicodeWrapIndented<'a> {
+    lines: UnicodeWrapWords<'a>,
+    continuation_prefix: &'a str,
+    continuation_width: usize,
+    first: bool,
+}
+
+impl<'a> UnicodeWrapIndented<'a> {
+    pub(crate) fn new(
+        s: &'a str,
+        first_width: usize,
+        continuation_prefix: &'a str,
+        total_width: usize,
+    ) -> Self {
+        Self {
+            lines: UnicodeWrapWords::new(s, first_width),
+            continuation_prefix,
+            continuation_width: total_width
+                .saturating_sub(crate::graphemes_width(continuation_prefix)),
+            first: true,
+        }
+    }
+
+    /// Returns the part of the string not yielded yet.
+    pub fn remainder(&self) -> &'a str {
+        self.lines.remainder()
+    }
+}
+
+impl<'a> Iterator for UnicodeWrapIndented<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.first {
+            self.first = false;
+            let line = self.lines.next()?;
+            // the rest is wrapped to the columns left next to the prefix
+            self.lines = UnicodeWrapWords::new(self.lines.remainder(), self.continuation_width);
+            return Some(Cow::Borrowed(line));
+        }
+        let line = self.lines.next()?;
+        let mut indented =
+            String::with_capacity(self.continuation_prefix.len().saturating_add(line.len()));
+        indented.push_str(self.continuation_prefix);
+        indented.push_str(line);
+        Some(Cow::Owned(indented))
+    }
+}
+
+impl FusedIterator for UnicodeWrapIndented<'_> {}
+
+/// U+00AD SOFT HYPHEN
+const SOFT_HYPHEN: char = '\u{AD}';
+
+/// Calls `f` with each paragraph in `text`, or `None` for each blank line.
+fn for_each_paragraph<'a>(text: &'a str, mut f: impl FnMut(Option<&'a str>)) {
+    let mut paragraph: Option<(usize, usize)> = None;
+    let mut offset = 0usize;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        // unwrap is safe as the sum is at most the length of text
+        offset = offset.checked_add(line.len()).unwrap();
+        if line.trim().is_empty() {
+            if let Some((para_start, para_end)) = paragraph.take() {
+                f(Some(&text[para_start..para_end]));
+            }
+            f(None);
+        } else {
+            let end = start
+                .checked_add(line.trim_end_matches(&['\n', '\r'][..]).len())
+                .unwrap();
+            paragraph = Some((paragraph.map_or(start, |(para_start, _)| para_start), end));
+        }
+    }
+    if let Some((para_start, para_end)) = paragraph {
+        f(Some(&text[para_start..para_end]));
+    }
+}
+
+/// Greedily wraps a paragraph, in which newlines are treated as spaces.
+///
+/// Each line is passed to `emit` as a slice of the paragraph, together with whether it was broken
+/// at a soft hyphen, in which case a column is reserved for a visible hyphen. Soft hyphens are
+/// only considered when `hyphenate` is set.
+fn wrap_paragraph<'a>(
+    paragraph: &'a str,
+    line_width: usize,
+    hyphenate: bool,
+    mut emit: impl FnMut(&'a str, bool),
+) {
+    // the current line as byte range and its display width
+    let mut line: Option<(usize, usize, usize)> = None;
+    // display width of the whitespace after the current line
+    let mut space_width = 0usize;
+
+    for (byte_index, word) in paragraph.split_word_bound_indices() {
+        if word.trim().is_empty() {
+            if line.is_some() {
+                // a newline is displayed as a space
+                let width = if word.contains(&['\n', '\r'][..]) {
+                    1
+                } else {
+                    crate::graphemes_width(word)
+                };
+                space_width = space_width.saturating_add(width);
+            }
+            continue;
+        }
+
+        let word_width = crate::graphemes_width(word);
+        let mut rest = word;
+        let mut rest_index = byte_index;
+        if let Some((start, end, width)) = line {
+            let used_width = width.saturating_add(space_width);
+            if used_width.saturating_add(word_width) <= line_width {
+                // unwrap is safe as the sum is at most the length of paragraph
+                line = Some((
+                    start,
+                    byte_index.checked_add(word.len()).unwrap(),
+                    used_width.saturating_add(word_width),
+                ));
+                space_width = 0;
+                continue;
+            }
+            match soft_hyphen_break(hyphenate, word, line_width.saturating_sub(used_width)) {
+                Some(hyphen) => {
+                    // unwrap is safe as the sum is at most the length of paragraph
+                    let hyphen_index = byte_index.checked_add(hyphen).unwrap();
+                    emit(&paragraph[start..hyphen_index], true);
+                    rest = &word[hyphen.saturating_add(SOFT_HYPHEN.len_utf8())..];
+                    rest_index = hyphen_index.checked_add(SOFT_HYPHEN.len_utf8()).unwrap();
+                }
+                None => emit(&paragraph[start..end], false),
+            }
+        }
+
+        // start a new line with the rest of the word, breaking it if it's too wide
+        space_width = 0;
+        line = None;
+        while !rest.is_empty() {
+            let (mut chunk, chunk_width) = rest.unicode_truncate(line_width);
+            if chunk.len() == rest.len() {
+                // unwrap is safe as the sum is at most the length of paragraph
+                line = Some((
+                    rest_index,
+                    rest_index.checked_add(chunk.len()).unwrap(),
+                    chunk_width,
+                ));
+                break;
+            }
+            let mut hyphenated = false;
+            if let Some(hyphen) = soft_hyphen_break(hyphenate, rest, line_width) {
+                chunk = &rest[..hyphen];
+                hyphenated = true;
+            } else if chunk_width == 0 {
+                // a grapheme wider than the line, put it on a line by itself
+                let end = rest
+                    .grapheme_indices(true)
+                    .find(|(_, grapheme)| grapheme.width() > 0)
+                    .map_or(rest.len(), |(byte_index, grapheme)| {
+                        byte_index.saturating_add(grapheme.len())
+                    });
+                chunk = &rest[..end];
+            }
+            emit(chunk, hyphenated);
+            let consumed = if hyphenated {
+                chunk.len().saturating_add(SOFT_HYPHEN.len_utf8())
+            } else {
+                chunk.len()
+            };
+            rest = &rest[consumed..];
+            // unwrap is safe as the sum is at most the length of paragraph
+            rest_index = rest_index.checked_add(consumed).unwrap();
+        }
+    }
+
+    if let Some((start, end, _)) = line {
+        emit(&paragraph[start..end], false);
+    }
+}
+
+/// Finds the last soft hyphen in `word` to break at, such that the part before it and a visible
+/// hyphen fit in `available_width`. Always `None` when `hyphenate` is not set.
+fn soft_hyphen_break(hyphenate: bool, word: &str, available_width: usize) -> Option<usize> {
+    if !hyphenate {
+        return None;
+    }
+    let mut width = 0usize;
+    let mut found = None;
+    for (byte_index, grapheme) in word.grapheme_indices(true) {
+        if width >= available_width {
+            break;
+        }
+        if grapheme.starts_with(SOFT_HYPHEN) && byte_index > 0 {
+            found = Some(byte_index);
+        }
+        width = width.saturating_add(grapheme.width());
+    }
+    found
+}
+
+/// Replaces each newline in a line with a space.
+fn newlines_as_spaces(line: &str) -> Cow<'_, str> {
+    if line.contains(&['\n', '\r'][..]) {
+        Cow::Owned(line.replace("\r\n", " ").replace(&['\n', '\r'][..], " "))
+    } else {
+        Cow::Borrowed(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_measured_like_truncate() {
+        for input in [
+            "a \u{1F3FD}\u{200D}\u{1F44B}",
+            "y\u{0306}es a\u{200B}b 你好 👨‍👩‍👧‍👦 \u{1F1E6}\u{1F1F8}",
+            "e\u{301}\u{301} \u{200B}\u{200B} hy\u{AD}phen",
+        ] {
+            // no grapheme is wider than 3 columns, the space joined with the modifier
+            for line_width in 3..8 {
+                for line in unicode_wrap_words(input, line_width) {
+                    assert_eq!(line.unicode_truncate(line_width).0, line, "{:?}", line);
+                }
+                for line in unicode_hyphenate_wrap(input, line_width) {
+                    assert_eq!(line.unicode_truncate(line_width).0, line, "{:?}", line);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flow_columns_at_words() {
+        assert_eq!(
+            flow_columns("The quick brown fox jumps.", 10, 3),
+            [("The quick", 9), ("brown fox", 9), ("jumps.", 6)]
+        );
+    }
+
+    #[test]
+    fn flow_columns_limited() {
+        assert_eq!(
+            flow_columns("The quick brown fox jumps.", 10, 2),
+            [("The quick", 9), ("brown fox", 9)]
+        );
+        assert!(flow_columns("The quick", 10, 0).is_empty());
+    }
+
+    #[test]
+    fn flow_columns_short_text() {
+        assert_eq!(flow_columns("  The  ", 10, 3), [("The", 3)]);
+        assert!(flow_columns("", 10, 3).is_empty());
+        assert!(flow_columns(" \n ", 10, 3).is_empty());
+    }
+
+    #[test]
+    fn flow_columns_newline() {
+        assert_eq!(
+            flow_columns("one\r\n\ntwo three", 10, 3),
+            [("one", 3), ("two three", 9)]
+        );
+    }
+
+    #[test]
+    fn flow_columns_long_words() {
+        assert_eq!(
+            flow_columns("abcdefgh ij", 3, 4),
+            [("abc", 3), ("def", 3), ("gh", 2), ("ij", 2)]
+        );
+        assert_eq!(
+            flow_columns("你好吗你好", 5, 3),
+            [("你好", 4), ("吗你", 4), ("好", 2)]
+        );
+        assert_eq!(flow_columns("a你b", 1, 3), [("a", 1), ("你", 2), ("b", 1)]);
+    }
+
+    #[test]
+    fn clamp_lines_not_clamped() {
+        assert_eq!(
+            clamp_lines("The quick brown fox", 10, 2, "…"),
+            (vec!["The quick".into(), "brown fox".into()], false)
+        );
+        assert_eq!(clamp_lines("", 10, 2, "…"), (vec![], false));
+        assert_eq!(clamp_lines("", 10, 0, "…"), (vec![], false));
+    }
+
+    #[test]
+    fn clamp_lines_reserves_ellipsis() {
+        // "brown fox" would fit on the last line without the ellipsis
+        assert_eq!(
+            clamp_lines("The quick brown fox jumps.", 10, 2, "...").0,
+            ["The quick", "brown..."]
+        );
+        assert_eq!(
+            clamp_lines("你好吗你好吗", 4, 2, "…"),
+            (vec!["你好".into(), "吗…".into()], true)
+        );
+    }
+
+    #[test]
+    fn clamp_lines_newlines_count() {
+        assert_eq!(
+            clamp_lines("one\ntwo\nthree", 10, 2, "…"),
+            (vec!["one".into(), "two…".into()], true)
+        );
+        assert_eq!(
+            clamp_lines("one\n\ntwo", 10, 2, "…"),
+            (vec!["one".into(), "…".into()], true)
+        );
+    }
+
+    #[test]
+    fn clamp_lines_no_lines() {
+        assert_eq!(clamp_lines("abc", 10, 0, "…"), (vec![], true));
+    }
+
+    #[test]
+    fn clamp_lines_trailing_zwj() {
+        assert_eq!(
+            clamp_lines("😀\u{200D}\nabc", 10, 1, "👋"),
+            (vec!["😀👋".into()], true)
+        );
+    }
+
+    #[test]
+    fn clamp_lines_narrow() {
+        assert_eq!(clamp_lines("你好吗", 2, 1, "…"), (vec!["…".into()], true));
+        assert_eq!(
+            clamp_lines("abc", 2, 1, "......"),
+            (vec!["..".into()], true)
+        );
+    }
+
+    #[test]
+    fn fit_box_exact_width() {
+        for text in [
+            "The quick brown fox",
+            "你好吗你好吗",
+            "a你b\n\nc\u{0306}d",
+            "你",
+        ] {
+            for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+                for width in 0..8 {
+                    for height in 0..4 {
+                        let fit = fit_box(text, width, height, align, true);
+                        assert_eq!(fit.lines.len(), height);
+                        assert!(fit.used_height <= height);
+                        for line in &fit.lines {
+                            assert_eq!(line.width(), width, "{:?}", fit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fit_box_truncated() {
+        let fit = fit_box("one\ntwo\nthree", 5, 2, Alignment::Right, false);
+        assert_eq!(
+            fit,
+            BoxFit {
+                lines: vec!["  one".into(), "  two".into()],
+                truncated: true,
+                used_height: 2,
+            }
+        );
+        assert!(!fit_box("one\ntwo", 5, 2, Alignment::Right, false).truncated);
+    }
+
+    #[test]
+    fn fit_box_empty() {
+        let fit = fit_box("", 3, 2, Alignment::Left, false);
+        assert!(fit.lines.is_empty());
+        assert!(!fit.truncated);
+        assert_eq!(fit.used_height, 0);
+        let fit = fit_box("", 3, 2, Alignment::Left, true);
+        assert_eq!(fit.lines, ["   ", "   "]);
+        assert_eq!(fit.used_height, 0);
+    }
+
+    #[test]
+    fn fit_box_grapheme_too_wide() {
+        let fit = fit_box("a你b", 1, 3, Alignment::Left, false);
+        assert_eq!(fit.lines, ["a", " ", "b"]);
+        assert!(fit.truncated);
+
+        let fit = fit_box("你", 1, 3, Alignment::Left, false);
+        assert_eq!(fit.lines, [" "]);
+        assert!(fit.truncated);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(unicode_wrap_words("", 10).is_empty());
+    }
+
+    #[test]
+    fn fits() {
+        assert_eq!(unicode_wrap_words("hello world", 11), ["hello world"]);
+    }
+
+    #[test]
+    fn greedy() {
+        assert_eq!(unicode_wrap_words("aa bb cc dd", 5), ["aa bb", "cc dd"]);
+        assert_eq!(unicode_wrap_words("aa   bb cc", 5), ["aa", "bb cc"]);
+    }
+
+    #[test]
+    fn lines_are_borrowed() {
+        let lines = unicode_wrap_words("aa bb cc dd", 5);
+        assert!(lines.iter().all(|line| matches!(line, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn wide_chars() {
+        assert_eq!(
+            unicode_wrap_words("你好吗你好吗", 5),
+            ["你好", "吗你", "好吗"]
+        );
+        assert_eq!(unicode_wrap_words("hi 你好吗", 6), ["hi 你", "好吗"]);
+    }
+
+    #[test]
+    fn long_word() {
+        assert_eq!(
+            unicode_wrap_words("a abcdefg b", 3),
+            ["a", "abc", "def", "g b"]
+        );
+    }
+
+    #[test]
+    fn grapheme_wider_than_line() {
+        assert_eq!(unicode_wrap_words("你好", 1), ["你", "好"]);
+        assert_eq!(unicode_wrap_words("ab", 0), ["a", "b"]);
+    }
+
+    #[test]
+    fn newline_as_space() {
+        let lines = unicode_wrap_words("aa\nbb\r\ncc", 8);
+        assert_eq!(lines, ["aa bb cc"]);
+        assert!(matches!(lines[0], Cow::Owned(_)));
+        assert_eq!(unicode_wrap_words("aa\nbb\ncc", 5), ["aa bb", "cc"]);
+    }
+
+    #[test]
+    fn hyphenate_long_word() {
+        let text = "hy\u{AD}phen\u{AD}ation";
+        assert_eq!(unicode_hyphenate_wrap(text, 20), ["hyphenation"]);
+        assert_eq!(unicode_hyphenate_wrap(text, 9), ["hyphen-", "ation"]);
+        assert_eq!(unicode_hyphenate_wrap(text, 6), ["hy-", "phen-", "ation"]);
+        // no soft hyphen fits, fall back to breaking between graphemes
+        assert_eq!(
+            unicode_hyphenate_wrap(text, 4),
+            ["hy-", "phen", "atio", "n"]
+        );
+        assert_eq!(
+            unicode_hyphenate_wrap(text, 2),
+            ["hy", "ph", "en", "at", "io", "n"]
+        );
+        assert_eq!(
+            unicode_hyphenate_wrap(text, 0),
+            ["h", "y", "p", "h", "e", "n", "a", "t", "i", "o", "n"]
+        );
+    }
+
+    #[test]
+    fn hyphenate_to_fill_line() {
+        assert_eq!(
+            unicode_hyphenate_wrap("a hy\u{AD}phen\u{AD}ation b", 8),
+            ["a hy-", "phen-", "ation b"]
+        );
+        assert_eq!(
+            unicode_hyphenate_wrap("abc hy\u{AD}phen", 5),
+            ["abc", "hy-", "phen"]
+        );
+    }
+
+    #[test]
+    fn hyphenate_wide_chars() {
+        // fullwidth latin letters form words
+        assert_eq!(
+            unicode_hyphenate_wrap("ｈｙ\u{AD}ｐｈｅｎ", 6),
+            ["ｈｙ-", "ｐｈｅ", "ｎ"]
+        );
+        // ideographs are separate words, so there is no need for a hyphen
+        assert_eq!(
+            unicode_hyphenate_wrap("你好\u{AD}吗你", 5),
+            ["你好", "吗你"]
+        );
+    }
+
+    #[test]
+    fn hyphenate_without_soft_hyphens() {
+        let text = "aa bb\ncc\n\n你好吗你好吗";
+        let expected: Vec<_> = unicode_wrap_words(text, 5)
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect();
+        assert_eq!(unicode_hyphenate_wrap(text, 5), expected);
+    }
+
+    #[test]
+    fn paragraphs() {
+        assert_eq!(
+            unicode_wrap_words("aa bb\n\ncc dd", 20),
+            ["aa bb", "", "cc dd"]
+        );
+        assert_eq!(
+            unicode_wrap_words("aa bb\r\n\r\n\r\ncc dd\n", 2),
+            ["aa", "bb", "", "", "cc", "dd"]
+        );
+        assert_eq!(unicode_wrap_words("\naa\n  \nbb", 2), ["", "aa", "", "bb"]);
+    }
+
+    #[test]
+    fn indented_first_line_borrowed() {
+        let mut lines = "The quick brown fox".unicode_wrap_indented(10, "> ", 8);
+        assert!(matches!(lines.next(), Some(Cow::Borrowed("The quick"))));
+        assert_eq!(lines.collect::<Vec<_>>(), ["> brown", "> fox"]);
+    }
+
+    #[test]
+    fn indented_wide_prefix() {
+        // the prefix takes 4 columns, leaving 3 for the text
+        let lines: Vec<_> = "你好吗 abc".unicode_wrap_indented(2, "↳你 ", 7).collect();
+        assert_eq!(lines, ["你", "↳你 好", "↳你 吗", "↳你 abc"]);
+        for line in &lines {
+            assert!(line.unicode_truncate(7).0.len() == line.len(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn indented_prefix_too_wide() {
+        let lines: Vec<_> = "abc".unicode_wrap_indented(1, "----", 3).collect();
+        assert_eq!(lines, ["a", "----b", "----c"]);
+    }
+
+    #[test]
+    fn indented_newlines() {
+        let lines: Vec<_> = "one\n\ntwo".unicode_wrap_indented(10, "  ", 10).collect();
+        assert_eq!(lines, ["one", "  ", "  two"]);
+        assert_eq!("".unicode_wrap_indented(10, "  ", 10).next(), None);
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2019 Aetf <aetf at unlimitedcodeworks dot xyz>.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Width policy for emoji sequences.
+
+use unicode_width::UnicodeWidthStr;
+
+/// U+200D ZERO WIDTH JOINER
+pub(crate) const ZWJ: char = '\u{200D}';
+
+/// Policy for measuring grapheme clusters made of several emoji, i.e. ZWJ sequences like
+/// `"👨‍👩‍👧‍👦"` and emoji modifier sequences like `"👋🏽"`.
+///
+/// [`unicode_width`] measures recognized sequences as a single glyph, but terminals differ in
+/// whether they actually render them joined. Other grapheme clusters are measured the same under
+/// every policy.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ZwjWidth {
+    /// Sum of the widths of the individual emoji, as rendered by terminals without support for
+    /// joining. `"👨‍👩‍👧‍👦"` is 8 columns wide.
+    Sum,
+    /// Width of the first emoji (including its presentation selector), as rendered by terminals
+    /// joining the sequence into one glyph. `"👨‍👩‍👧‍👦"` is 2 columns wide.
+    Single,
+}
+
+impl ZwjWidth {
+    /// Returns the display width of a grapheme cluster under this policy.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use unicode_truncate::ZwjWidth;
+    ///
+    /// assert_eq!(ZwjWidth::Sum.grapheme_width("👨‍👩‍👧‍👦"), 8);
+    /// assert_eq!(ZwjWidth::Single.grapheme_width("👨‍👩‍👧‍👦"), 2);
+    /// assert_eq!(ZwjWidth::Sum.grapheme_width("你"), 2);
+    /// ```
+    pub fn grapheme_width(self, grapheme: &str) -> usize {
+        if !grapheme.chars().any(|c| c == ZWJ || is_emoji_modifier(c)) {
+            return grapheme.width();
+        }
+        match self {
+            ZwjWidth::Sum => emoji_parts(grapheme)
+                .map(UnicodeWidthStr::width)
+                .fold(0, usize::saturating_add),
+            ZwjWidth::Single => emoji_parts(grapheme)
+                .next()
+                .map_or(0, UnicodeWidthStr::width),
+        }
+    }
+}
+
+/// Emoji modifiers (skin tones) U+1F3FB..U+1F3FF
+fn is_emoji_modifier(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+/// Splits a sequence into the individual emoji, dropping the joiners. Modifiers are split into
+/// their own part, as they are displayed as a color swatch when not applied to the base.
+fn emoji_parts(grapheme: &str) -> impl Iterator<Item = &str> {
+    grapheme.split(ZWJ).flat_map(|part| {
+        let mut rest = part;
+        core::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let split = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| is_emoji_modifier(c))
+                .map_or(rest.len(), |(byte_index, _)| byte_index);
+            let (emoji, remaining) = rest.split_at(split);
+            rest = remaining;
+            Some(emoji)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_graphemes() {
+        for policy in [ZwjWidth::Sum, ZwjWidth::Single] {
+            assert_eq!(policy.grapheme_width("a"), 1);
+            assert_eq!(policy.grapheme_width("y\u{0306}"), 1);
+            assert_eq!(policy.grapheme_width("🇺🇸"), 2);
+            assert_eq!(policy.grapheme_width("\u{200D}"), 0);
+        }
+    }
+
+    #[test]
+    fn zwj_sequence() {
+        assert_eq!(ZwjWidth::Sum.grapheme_width("👨‍👩‍👧‍👦"), 8);
+        assert_eq!(ZwjWidth::Single.grapheme_width("👨‍👩‍👧‍👦"), 2);
+        // heart on fire, with a presentation selector after the heart
+        assert_eq!(ZwjWidth::Sum.grapheme_width("❤\u{FE0F}\u{200D}🔥"), 4);
+        assert_eq!(ZwjWidth::Single.grapheme_width("❤\u{FE0F}\u{200D}🔥"), 2);
+    }
+
+    #[test]
+    fn modifier_sequence() {
+        assert_eq!(ZwjWidth::Sum.grapheme_width("👋🏽"), 4);
+        assert_eq!(ZwjWidth::Single.grapheme_width("👋🏽"), 2);
+        assert_eq!(ZwjWidth::Sum.grapheme_width("👩🏽\u{200D}🚀"), 6);
+        assert_eq!(ZwjWidth::Single.grapheme_width("👩🏽\u{200D}🚀"), 2);
+    }
+}
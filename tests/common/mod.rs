@@ -0,0 +1,21 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use proptest::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Strings mixing ASCII, control characters, combining marks, zero-width characters, wide
+/// characters and emoji sequences.
+pub const CORPUS: &str = "[a-z\u{0}-\u{1F}\u{7F}-\u{9F}\u{300}\u{301}\u{200B}-\u{200F}\u{FE0F}\u{1100}\u{4F60}\u{1F1E6}-\u{1F1FF}\u{1F44B}\u{1F468}\u{1F3FB}-\u{1F3FF}\u{20E3}#\r\n]{0,32}";
+
+/// A string from [`CORPUS`] and a maximum width to truncate it to.
+pub fn corpus() -> impl Strategy<Value = (String, usize)> {
+    (CORPUS, 0usize..32)
+}
+
+/// The width the truncation methods return, i.e. the sum of the widths of the graphemes.
+pub fn graphemes_width(s: &str) -> usize {
+    s.graphemes(true).map(UnicodeWidthStr::width).sum()
+}
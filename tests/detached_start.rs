@@ -0,0 +1,54 @@
+mod common;
+
+use proptest::prelude::*;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_truncate::UnicodeTruncateStr;
+use unicode_width::UnicodeWidthStr;
+
+/// Whether a grapheme would attach to whatever is rendered before it, as it is zero-width or starts
+/// with a character extending a preceding base.
+fn is_detached(grapheme: &str) -> bool {
+    let first = grapheme.chars().next().unwrap();
+    grapheme.width() == 0 || format!("a{}", first).graphemes(true).count() == 1
+}
+
+fn starts_detached(s: &str) -> bool {
+    s.graphemes(true).next().map_or(false, is_detached)
+}
+
+#[test]
+fn marks_after_cut() {
+    let input = "a\u{0301}b\u{0301}c";
+    for max_width in 0..4 {
+        let (result, _) = input.unicode_truncate_start(max_width);
+        assert!(
+            !starts_detached(result),
+            "{:?} at width {}",
+            result,
+            max_width
+        );
+        let (result, _) = input.unicode_truncate_centered(max_width);
+        assert!(
+            !starts_detached(result),
+            "{:?} at width {}",
+            result,
+            max_width
+        );
+    }
+}
+
+proptest! {
+    #[test]
+    fn truncated_result_not_detached(
+        (s, max_width) in common::corpus(),
+    ) {
+        for (result, _) in [
+            s.unicode_truncate_start(max_width),
+            s.unicode_truncate_centered(max_width),
+        ] {
+            if result.len() < s.len() {
+                prop_assert!(!starts_detached(result), "{:?}", result);
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use proptest::prelude::*;
+use unicode_truncate::{UnicodeTruncateStr, UnicodeTruncateString};
+
+proptest! {
+    #[test]
+    fn same_as_truncate(
+        (s, max_width) in common::corpus(),
+    ) {
+        let (expected, expected_width) = s.unicode_truncate(max_width);
+        let mut truncated = s.clone();
+        let (width, removed) = truncated.unicode_truncate_in_place(max_width);
+        prop_assert_eq!(truncated.as_str(), expected);
+        prop_assert_eq!(width, expected_width);
+        prop_assert_eq!(removed, expected.len() < s.len());
+    }
+
+    #[test]
+    fn same_as_truncate_start(
+        (s, max_width) in common::corpus(),
+    ) {
+        let (expected, expected_width) = s.unicode_truncate_start(max_width);
+        let mut truncated = s.clone();
+        let width = truncated.unicode_truncate_start_in_place(max_width);
+        prop_assert_eq!(truncated.as_str(), expected);
+        prop_assert_eq!(width, expected_width);
+    }
+}
@@ -7,3 +7,13 @@ fn main() {
 
     assert_eq!("你好吗".unicode_pad(5, Alignment::Left, true), "你好 ");
 }
+
+#[test]
+fn empty_aligned() {
+    for align in [Alignment::Left, Alignment::Center, Alignment::Right] {
+        assert_eq!("".unicode_truncate_aligned(0, align), ("", 0));
+        for max_width in [1, 2, 10, usize::MAX] {
+            assert_eq!("".unicode_truncate_aligned(max_width, align), ("", 0));
+        }
+    }
+}
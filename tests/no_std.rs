@@ -1,8 +1,20 @@
 #![no_std]
 
-use unicode_truncate::UnicodeTruncateStr;
+use unicode_truncate::{Alignment, UnicodeTruncateStr};
 
 #[test]
 fn main() {
     assert_eq!("你好吗".unicode_truncate(5), ("你好", 4));
+
+    // chunks can be collected without an allocator
+    let mut chunks = [("", 0); 4];
+    let mut count = 0;
+    for (slot, chunk) in chunks.iter_mut().zip("你好吗abc".unicode_chunks(3)) {
+        *slot = chunk;
+        count += 1;
+    }
+    assert_eq!(count, 4);
+    assert_eq!(chunks, [("你", 2), ("好", 2), ("吗a", 3), ("bc", 2)]);
+
+    assert_eq!(Alignment::Left.opposite(), Alignment::Right);
 }
@@ -0,0 +1,20 @@
+mod common;
+
+use proptest::prelude::*;
+use unicode_truncate::UnicodeTruncateStr;
+
+proptest! {
+    #[test]
+    fn returned_width_is_result_width(
+        (s, max_width) in common::corpus(),
+    ) {
+        for (result, width) in [
+            s.unicode_truncate(max_width),
+            s.unicode_truncate_start(max_width),
+            s.unicode_truncate_centered(max_width),
+        ] {
+            prop_assert_eq!(width, common::graphemes_width(result));
+            prop_assert!(width <= max_width);
+        }
+    }
+}
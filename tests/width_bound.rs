@@ -0,0 +1,20 @@
+mod common;
+
+use proptest::prelude::*;
+use unicode_width::UnicodeWidthStr;
+
+proptest! {
+    /// The truncation methods return early when the length in bytes fits, assuming the display
+    /// width never exceeds it.
+    #[test]
+    fn width_bounded_by_len(s in any::<String>()) {
+        prop_assert!(s.width() <= s.len());
+        prop_assert!(common::graphemes_width(&s) <= s.len());
+    }
+
+    #[test]
+    fn width_bounded_by_len_sequences(s in common::CORPUS) {
+        prop_assert!(s.width() <= s.len());
+        prop_assert!(common::graphemes_width(&s) <= s.len());
+    }
+}